@@ -1,11 +1,47 @@
 use serde::Deserialize;
 use std::fs;
 
+use crate::model::IntegrationMode;
+use crate::scheduler::{CallPointConfig, HandoffMode};
+
+/// Sliding window size the [`speed_profile_calculator::SpeedProfileCalculator`]
+/// feeding `call_points` keeps, when unspecified in the track config.
+fn default_profile_window() -> usize {
+    32
+}
+
+/// Lookahead distance (meters) the same calculator is built with, when
+/// unspecified.
+fn default_profile_lookahead_m() -> f64 {
+    200.0
+}
+
 #[derive(Deserialize, Debug)]
 pub struct TimerConfig {
     pub pit_entry_m: f64,
     pub call_offset_m: f64,
     pub buffer_s: f64,
+    /// How `time_to_call` integrates the speed profile. Defaults to
+    /// trapezoidal so existing track configs are unaffected.
+    #[serde(default)]
+    pub interpolation: IntegrationMode,
+    /// Candidate multi-point call schedule for [`crate::scheduler::CallScheduler`].
+    /// Empty (the default) keeps existing track configs on the single-point
+    /// `pit_entry_m`/`call_offset_m` behavior in `model::time_to_call`.
+    #[serde(default)]
+    pub call_points: Vec<CallPointConfig>,
+    /// Hand-off behavior between consecutive `call_points`. Unused when
+    /// `call_points` is empty.
+    #[serde(default)]
+    pub handoff: HandoffMode,
+    /// `SpeedProfileCalculator` window size feeding `call_points` integration.
+    /// Unused when `call_points` is empty.
+    #[serde(default = "default_profile_window")]
+    pub profile_window: usize,
+    /// `SpeedProfileCalculator` lookahead (meters) feeding `call_points`
+    /// integration. Unused when `call_points` is empty.
+    #[serde(default = "default_profile_lookahead_m")]
+    pub profile_lookahead_m: f64,
 }
 
 impl TimerConfig {
@@ -19,6 +19,21 @@ pub struct TelemetryPacket {
     pub speed_profile: Option<Vec<SpeedSample>>,
 }
 
+/// Selects how `integrate_time_over_profile` turns a sparse `(x_m, v_mps)`
+/// profile into `dt = ∫ (1/v(x)) dx`.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum IntegrationMode {
+    /// Piecewise-linear speed between samples, integrated with the trapezoidal rule.
+    /// Cheap, and fine when samples are dense relative to how fast v(x) changes.
+    #[default]
+    Trapezoidal,
+    /// Shape-preserving (Fritsch–Carlson) monotone cubic Hermite spline through the
+    /// samples, integrated with Simpson's rule. Avoids the kinks trapezoidal
+    /// integration introduces through sparsely-sampled braking/acceleration zones.
+    MonotoneCubic,
+}
+
 pub fn time_to_call(d: &TelemetryPacket, cfg: &TimerConfig) -> (f64, f64, &'static str) {
     // Core math per spec:
     // d_rem = max((pit_entry_m - call_offset_m) - lap_distance_m, 0)
@@ -27,25 +42,29 @@ pub fn time_to_call(d: &TelemetryPacket, cfg: &TimerConfig) -> (f64, f64, &'stat
     let end_x = call_at_m;
     let d_rem = (end_x - start_x).max(0.0);
 
-    // Prefer integrated time over a provided speed profile (trapezoidal rule),
-    // otherwise fall back to instantaneous speed estimate.
+    // Prefer integrated time over a provided speed profile, otherwise fall back
+    // to instantaneous speed estimate.
     let v_inst_mps = (d.speed_kph / 3.6).max(0.1); // avoid div-by-zero; tiny epsilon
-    let t_call = integrate_time_over_profile(start_x, end_x, d.speed_profile.as_deref(), v_inst_mps)
-        .unwrap_or_else(|| d_rem / v_inst_mps);
+    let t_call = integrate_time_over_profile(
+        start_x,
+        end_x,
+        d.speed_profile.as_deref(),
+        v_inst_mps,
+        cfg.interpolation,
+    )
+    .unwrap_or_else(|| d_rem / v_inst_mps);
 
     // Latest safe radio moment: t_safe = t_call - buffer_s
     let t_safe = t_call - cfg.buffer_s;
+    let status = call_status(t_safe);
+
+    (t_call, t_safe, status)
+}
 
-    // let status = if t_safe < 0.0 {
-    //     "LOCKED_OUT"
-    // } else if t_safe < 2.0 {
-    //     "RED"
-    // } else if t_safe < 5.0 {
-    //     "AMBER"
-    // } else {
-    //     "GREEN"
-    // };
-    let status = if t_safe < 0.0 {
+/// Classify a `t_safe` margin into the traffic-light status shared by
+/// `time_to_call` and the multi-point `CallScheduler`.
+pub(crate) fn call_status(t_safe: f64) -> &'static str {
+    if t_safe < 0.0 {
         "LOCKED_OUT"
     } else if t_safe < 2.0 {
         "RED"
@@ -53,18 +72,18 @@ pub fn time_to_call(d: &TelemetryPacket, cfg: &TimerConfig) -> (f64, f64, &'stat
         "AMBER"
     } else {
         "GREEN"
-    };
-
-    (t_call, t_safe, status)
+    }
 }
 
-/// Trapezoidal integration of dt = ∫ (1 / v(x)) dx from start_x to end_x.
-/// Returns None if there are no usable samples in-range; caller should fall back.
-fn integrate_time_over_profile(
+/// Integration of dt = ∫ (1 / v(x)) dx from start_x to end_x over a sparse
+/// speed profile, per `mode`. Returns None if there are no usable samples
+/// in-range; caller should fall back to the instantaneous-speed estimate.
+pub(crate) fn integrate_time_over_profile(
     start_x: f64,
     end_x: f64,
     profile: Option<&[SpeedSample]>,
     fallback_v_mps: f64,
+    mode: IntegrationMode,
 ) -> Option<f64> {
     let Some(samples) = profile else { return None };
     if end_x <= start_x {
@@ -106,8 +125,17 @@ fn integrate_time_over_profile(
     pts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
     pts.dedup_by(|a, b| a.0 == b.0);
 
-    // Trapezoidal integrate of 1/v(x)
     let eps = 0.1_f64; // minimum speed to avoid blow-ups
+    let area = match mode {
+        IntegrationMode::Trapezoidal => integrate_trapezoidal(&pts, eps),
+        IntegrationMode::MonotoneCubic => integrate_monotone_cubic(&pts, eps),
+    };
+
+    Some(area)
+}
+
+/// Trapezoidal integration of 1/v(x) over piecewise-linear v(x).
+fn integrate_trapezoidal(pts: &[(f64, f64)], eps: f64) -> f64 {
     let mut area = 0.0;
     for w in pts.windows(2) {
         let (x0, v0) = w[0];
@@ -119,6 +147,100 @@ fn integrate_time_over_profile(
         let inv1 = 1.0 / v1;
         area += (x1 - x0) * 0.5 * (inv0 + inv1);
     }
+    area
+}
 
-    Some(area)
+/// Number of Simpson sub-intervals per Hermite segment when integrating the
+/// monotone cubic spline. High enough that the piecewise-cubic error is
+/// negligible next to sample noise, cheap enough to pay per telemetry tick.
+const CUBIC_SIMPSON_SUBDIVISIONS: usize = 8;
+
+/// Fritsch–Carlson shape-preserving tangents for a monotone cubic Hermite
+/// spline through `pts`. Interior tangents are a weighted harmonic mean of the
+/// adjacent secant slopes, forced to zero at local extrema (or when either
+/// secant is zero) so the spline never overshoots between samples — this is
+/// what keeps interpolated v(x) from dipping negative.
+fn fritsch_carlson_tangents(pts: &[(f64, f64)]) -> Vec<f64> {
+    let n = pts.len();
+    if n < 2 {
+        return vec![0.0; n];
+    }
+
+    let secants: Vec<f64> = pts
+        .windows(2)
+        .map(|w| {
+            let (x0, v0) = w[0];
+            let (x1, v1) = w[1];
+            (v1 - v0) / (x1 - x0)
+        })
+        .collect();
+
+    let mut tangents = vec![0.0; n];
+    tangents[0] = secants[0];
+    tangents[n - 1] = secants[n - 2];
+
+    for k in 1..n - 1 {
+        let d0 = secants[k - 1];
+        let d1 = secants[k];
+        if d0 == 0.0 || d1 == 0.0 || d0.signum() != d1.signum() {
+            tangents[k] = 0.0;
+            continue;
+        }
+        let (x_prev, _) = pts[k - 1];
+        let (x_k, _) = pts[k];
+        let (x_next, _) = pts[k + 1];
+        let w0 = 2.0 * (x_next - x_k) + (x_k - x_prev);
+        let w1 = (x_next - x_k) + 2.0 * (x_k - x_prev);
+        tangents[k] = (w0 + w1) / (w0 / d0 + w1 / d1);
+    }
+
+    tangents
+}
+
+/// Evaluate the cubic Hermite segment between `(x0, v0, m0)` and `(x1, v1, m1)`
+/// at `x`, clamped to `eps` so the reciprocal never blows up.
+fn hermite_eval(x0: f64, x1: f64, v0: f64, v1: f64, m0: f64, m1: f64, x: f64, eps: f64) -> f64 {
+    let h = x1 - x0;
+    let t = (x - x0) / h;
+    let t2 = t * t;
+    let t3 = t2 * t;
+    let h00 = 2.0 * t3 - 3.0 * t2 + 1.0;
+    let h10 = t3 - 2.0 * t2 + t;
+    let h01 = -2.0 * t3 + 3.0 * t2;
+    let h11 = t3 - t2;
+    let v = h00 * v0 + h10 * h * m0 + h01 * v1 + h11 * h * m1;
+    v.max(eps)
+}
+
+/// Integrate 1/v(x) over a monotone cubic Hermite spline fit to `pts`, via
+/// Simpson's rule on `CUBIC_SIMPSON_SUBDIVISIONS` sub-intervals per segment.
+fn integrate_monotone_cubic(pts: &[(f64, f64)], eps: f64) -> f64 {
+    if pts.len() < 2 {
+        return 0.0;
+    }
+    let tangents = fritsch_carlson_tangents(pts);
+
+    let k = CUBIC_SIMPSON_SUBDIVISIONS;
+    let mut area = 0.0;
+    for (i, w) in pts.windows(2).enumerate() {
+        let (x0, v0) = w[0];
+        let (x1, v1) = w[1];
+        if x1 <= x0 {
+            continue;
+        }
+        let m0 = tangents[i];
+        let m1 = tangents[i + 1];
+
+        let h = (x1 - x0) / k as f64;
+        let f = |x: f64| 1.0 / hermite_eval(x0, x1, v0, v1, m0, m1, x, eps);
+
+        // Composite Simpson's rule needs an even number of sub-intervals.
+        let mut sum = f(x0) + f(x1);
+        for j in 1..k {
+            let x = x0 + j as f64 * h;
+            sum += if j % 2 == 0 { 2.0 * f(x) } else { 4.0 * f(x) };
+        }
+        area += sum * h / 3.0;
+    }
+    area
 }
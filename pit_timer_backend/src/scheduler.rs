@@ -0,0 +1,260 @@
+use serde::{Deserialize, Serialize};
+use speed_profile_calculator::SpeedProfileCalculator;
+
+use crate::model::{self, SpeedSample};
+
+/// A half-open-by-convention lap distance range, in meters.
+#[derive(Deserialize, Debug, Clone, Copy)]
+pub struct DistanceRange {
+    pub lo_m: f64,
+    pub hi_m: f64,
+}
+
+impl DistanceRange {
+    fn contains(&self, x: f64) -> bool {
+        x >= self.lo_m && x <= self.hi_m
+    }
+}
+
+/// How the scheduler transitions between consecutive call points.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum HandoffMode {
+    /// The next call point can become eligible while the current one is
+    /// still in its window, so both may be armed (RED/AMBER) at once.
+    #[default]
+    Overlap,
+    /// The active call point is dropped the instant the car passes its
+    /// call distance, handing off to the next eligible point immediately.
+    Eager,
+}
+
+/// Static configuration for one candidate call point.
+#[derive(Deserialize, Debug, Clone)]
+pub struct CallPointConfig {
+    pub name: String,
+    /// Lap distance at which the radio call would be made.
+    pub call_at_m: f64,
+    /// Latest-safe-moment buffer, same semantics as `TimerConfig.buffer_s`.
+    pub buffer_s: f64,
+    /// The point is only eligible when `lap_distance_m` falls in at least
+    /// one of these ranges.
+    pub inclusion: Vec<DistanceRange>,
+    /// ...and outside every one of these (e.g. a DRS zone or blind corner).
+    #[serde(default)]
+    pub exclusion: Vec<DistanceRange>,
+    /// Minimum in-window telemetry samples required before this point may
+    /// arm, so it doesn't trust a near-empty integral.
+    #[serde(default)]
+    pub min_samples: usize,
+}
+
+impl CallPointConfig {
+    fn ranges_eligible(&self, lap_distance_m: f64) -> bool {
+        self.inclusion.iter().any(|r| r.contains(lap_distance_m))
+            && !self.exclusion.iter().any(|r| r.contains(lap_distance_m))
+    }
+}
+
+/// Computed `(t_call, t_safe, status)` for one call point at the current tick.
+#[derive(Debug, Clone, Serialize)]
+pub struct CallPointStatus {
+    pub name: String,
+    pub t_call: f64,
+    pub t_safe: f64,
+    pub status: &'static str,
+}
+
+/// What the scheduler reports for the current tick: the currently armed
+/// call point (if any) and the one queued to arm next.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SchedulerOutput {
+    pub active: Option<CallPointStatus>,
+    pub next: Option<CallPointStatus>,
+}
+
+/// Manages multiple candidate call points over a lap and decides which one
+/// is currently armed, per `HandoffMode`.
+///
+/// Call points are expected in ascending `call_at_m` order (the order a car
+/// encounters them over a lap).
+pub struct CallScheduler {
+    points: Vec<CallPointConfig>,
+    handoff: HandoffMode,
+    /// Index of the call point the scheduler last reported as active, so
+    /// hand-off only ever moves forward over a lap.
+    active_idx: usize,
+}
+
+impl CallScheduler {
+    pub fn new(points: Vec<CallPointConfig>, handoff: HandoffMode) -> Self {
+        Self { points, handoff, active_idx: 0 }
+    }
+
+    /// A point stays "in window" per `handoff`: under `Eager` it drops the
+    /// instant the car passes its own call distance; under `Overlap` it
+    /// stays in window for as long as its inclusion/exclusion ranges say so.
+    fn in_window(&self, point: &CallPointConfig, lap_distance_m: f64) -> bool {
+        if !point.ranges_eligible(lap_distance_m) {
+            return false;
+        }
+        match self.handoff {
+            HandoffMode::Eager => lap_distance_m < point.call_at_m,
+            HandoffMode::Overlap => true,
+        }
+    }
+
+    /// The car has actually driven past this point's call distance, as
+    /// opposed to simply not being in its window yet (e.g. still early in
+    /// the lap, before the point's inclusion range even starts).
+    fn passed(&self, point: &CallPointConfig, lap_distance_m: f64) -> bool {
+        lap_distance_m > point.call_at_m
+    }
+
+    fn armed(&self, point: &CallPointConfig, lap_distance_m: f64, profiles: &SpeedProfileCalculator) -> bool {
+        self.in_window(point, lap_distance_m)
+            && profiles.samples_in_range(lap_distance_m, point.call_at_m) >= point.min_samples
+    }
+
+    /// Recompute `(t_call, t_safe, status)` for `point` from the calculator's
+    /// current speed profile, reusing the same integration path as
+    /// `model::time_to_call`.
+    fn status_for(&self, point: &CallPointConfig, lap_distance_m: f64, speed_kph: f64, profiles: &SpeedProfileCalculator) -> CallPointStatus {
+        let d_rem = (point.call_at_m - lap_distance_m).max(0.0);
+        let v_inst_mps = (speed_kph / 3.6).max(0.1);
+
+        let profile: Option<Vec<SpeedSample>> = profiles
+            .get_profile(lap_distance_m, point.call_at_m)
+            .map(|report| report.samples.iter().map(|s| SpeedSample { x_m: s.x_m, v_mps: s.v_mps }).collect());
+
+        let t_call = model::integrate_time_over_profile(
+            lap_distance_m,
+            point.call_at_m,
+            profile.as_deref(),
+            v_inst_mps,
+            Default::default(),
+        )
+        .unwrap_or_else(|| d_rem / v_inst_mps);
+
+        let t_safe = t_call - point.buffer_s;
+        CallPointStatus {
+            name: point.name.clone(),
+            t_call,
+            t_safe,
+            status: model::call_status(t_safe),
+        }
+    }
+
+    /// Advance scheduling state for the current tick and report the active
+    /// and next-queued call points.
+    pub fn update(&mut self, lap_distance_m: f64, speed_kph: f64, profiles: &SpeedProfileCalculator) -> SchedulerOutput {
+        // Advance past any point the car has actually driven past and that's
+        // no longer in window, so hand-off only ever moves forward over a
+        // lap. Gating on `passed` too (not just `!in_window`) matters at the
+        // start of a lap: `lap_distance_m` starts near 0, which is out of
+        // every point's inclusion range, but the car hasn't passed anything
+        // yet so the scheduler must not skip straight past every point.
+        while self.active_idx < self.points.len()
+            && !self.in_window(&self.points[self.active_idx], lap_distance_m)
+            && self.passed(&self.points[self.active_idx], lap_distance_m)
+        {
+            self.active_idx += 1;
+        }
+
+        let active = self
+            .points
+            .get(self.active_idx)
+            .filter(|p| self.armed(p, lap_distance_m, profiles))
+            .map(|p| self.status_for(p, lap_distance_m, speed_kph, profiles));
+
+        let next = self
+            .points
+            .get(self.active_idx + 1..)
+            .unwrap_or(&[])
+            .iter()
+            .find(|p| p.ranges_eligible(lap_distance_m))
+            .filter(|p| self.armed(p, lap_distance_m, profiles))
+            .map(|p| self.status_for(p, lap_distance_m, speed_kph, profiles));
+
+        SchedulerOutput { active, next }
+    }
+
+    /// Reset hand-off back to the first configured call point (e.g. on a new lap).
+    pub fn reset(&mut self) {
+        self.active_idx = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(name: &str, call_at_m: f64, lo_m: f64, hi_m: f64) -> CallPointConfig {
+        CallPointConfig {
+            name: name.to_string(),
+            call_at_m,
+            buffer_s: 0.0,
+            inclusion: vec![DistanceRange { lo_m, hi_m }],
+            exclusion: Vec::new(),
+            min_samples: 0,
+        }
+    }
+
+    #[test]
+    fn update_does_not_panic_outside_every_call_window() {
+        let mut sched = CallScheduler::new(
+            vec![point("pit1", 1000.0, 900.0, 1100.0), point("pit2", 2000.0, 1900.0, 2100.0)],
+            HandoffMode::Overlap,
+        );
+        let profiles = SpeedProfileCalculator::new(16, 200.0);
+
+        // Distance outside every inclusion range, before the first call
+        // point, so the advance loop walks `active_idx` past the end.
+        let out = sched.update(0.0, 300.0, &profiles);
+        assert!(out.active.is_none());
+        assert!(out.next.is_none());
+
+        // Also check a distance past the last point's window.
+        let out = sched.update(2200.0, 300.0, &profiles);
+        assert!(out.active.is_none());
+        assert!(out.next.is_none());
+    }
+
+    #[test]
+    fn update_arms_once_the_car_reaches_a_call_points_inclusion_range() {
+        let mut sched = CallScheduler::new(vec![point("pit1", 1000.0, 900.0, 1100.0)], HandoffMode::Overlap);
+        let profiles = SpeedProfileCalculator::new(16, 200.0);
+
+        // Early in the lap, well before the inclusion range: nothing armed
+        // yet, but the point must still be reachable later in the lap.
+        let out = sched.update(0.0, 300.0, &profiles);
+        assert!(out.active.is_none());
+
+        // Car has now reached the point's inclusion range: it must arm.
+        let out = sched.update(950.0, 300.0, &profiles);
+        assert!(out.active.is_some());
+        assert_eq!(out.active.unwrap().name, "pit1");
+    }
+
+    #[test]
+    fn armed_requires_samples_within_the_remaining_distance_not_just_the_window() {
+        let mut point = point("pit1", 1000.0, 900.0, 1100.0);
+        point.min_samples = 2;
+        let mut sched = CallScheduler::new(vec![point], HandoffMode::Overlap);
+        let mut profiles = SpeedProfileCalculator::new(16, 200.0);
+
+        // Plenty of samples in the window overall, but all of them are well
+        // behind the car, outside [lap_distance_m, call_at_m] — shouldn't count.
+        profiles.add_sample(100.0, 300.0 * 3.6);
+        profiles.add_sample(200.0, 300.0 * 3.6);
+        profiles.add_sample(300.0, 300.0 * 3.6);
+        let out = sched.update(950.0, 300.0, &profiles);
+        assert!(out.active.is_none());
+
+        // Two more samples actually within [950, 1000]: now it arms.
+        profiles.add_sample(960.0, 300.0 * 3.6);
+        profiles.add_sample(970.0, 300.0 * 3.6);
+        let out = sched.update(950.0, 300.0, &profiles);
+        assert!(out.active.is_some());
+    }
+}
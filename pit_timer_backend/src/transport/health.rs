@@ -0,0 +1,60 @@
+use std::os::unix::io::RawFd;
+
+/// TCP-level health signal for one connection, read from the kernel's
+/// `TCP_INFO` socket option so operators — and the adaptive downsampler in
+/// [`super::ws`] — can tell link degradation apart from compute latency.
+#[derive(Debug, Clone, Copy, serde::Serialize)]
+pub struct TcpHealth {
+    pub rtt_ms: f64,
+    pub rtt_var_ms: f64,
+    pub retransmits: u32,
+}
+
+/// Keep-alive tuning applied to every accepted connection so a half-open
+/// socket (cable pulled, laptop slept) is detected and dropped instead of
+/// silently absorbing broadcast traffic forever.
+pub fn configure_keepalive(stream: &tokio::net::TcpStream) {
+    let sock = socket2::SockRef::from(stream);
+    let keepalive = socket2::TcpKeepalive::new()
+        .with_time(std::time::Duration::from_secs(10))
+        .with_interval(std::time::Duration::from_secs(5));
+    if let Err(e) = sock.set_tcp_keepalive(&keepalive) {
+        eprintln!("Failed to configure TCP keepalive: {}", e);
+    }
+}
+
+/// Read `TCP_INFO` for the raw socket `fd`, if the platform exposes it.
+#[cfg(target_os = "linux")]
+pub fn read_tcp_info(fd: RawFd) -> Option<TcpHealth> {
+    use std::mem;
+
+    let mut info: libc::tcp_info = unsafe { mem::zeroed() };
+    let mut len = mem::size_of::<libc::tcp_info>() as libc::socklen_t;
+
+    // SAFETY: `fd` is a live TCP socket owned by the caller for the duration
+    // of this call, and `info`/`len` are sized to exactly match what the
+    // kernel expects for `TCP_INFO`.
+    let ret = unsafe {
+        libc::getsockopt(
+            fd,
+            libc::IPPROTO_TCP,
+            libc::TCP_INFO,
+            &mut info as *mut _ as *mut libc::c_void,
+            &mut len,
+        )
+    };
+    if ret != 0 {
+        return None;
+    }
+
+    Some(TcpHealth {
+        rtt_ms: info.tcpi_rtt as f64 / 1000.0,
+        rtt_var_ms: info.tcpi_rttvar as f64 / 1000.0,
+        retransmits: info.tcpi_retransmits as u32,
+    })
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn read_tcp_info(_fd: RawFd) -> Option<TcpHealth> {
+    None
+}
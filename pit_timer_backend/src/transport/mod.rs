@@ -0,0 +1,69 @@
+pub mod health;
+pub mod quic;
+pub mod ws;
+
+use serde::Serialize;
+use std::path::PathBuf;
+
+use crate::scheduler::CallPointStatus;
+
+#[derive(Serialize, Debug, Clone)]
+pub struct TimerOut {
+    pub t_call: f64,
+    pub t_safe: f64,
+    pub status: &'static str,
+    pub lap_distance_m: f64,
+    pub speed_kph: f64,
+    /// Currently armed call point from `TimerConfig.call_points`, if any are
+    /// configured; `None` when the track config has no multi-point schedule
+    /// (single-point `t_call`/`t_safe`/`status` above still apply either way).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_call: Option<CallPointStatus>,
+    /// The call point queued to arm next, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub next_call: Option<CallPointStatus>,
+}
+
+/// Which listener `main` starts, selected via the `TRANSPORT` env var.
+/// Telemetry is latency-critical and loss-tolerant, so QUIC carries each
+/// packet as an unreliable datagram rather than paying WebSocket's
+/// head-of-line blocking; both modes share the same `model::time_to_call`
+/// compute path and fan out over the same `broadcast::channel`.
+pub enum Transport {
+    Ws,
+    Quic,
+}
+
+impl Transport {
+    pub fn from_env() -> Self {
+        match std::env::var("TRANSPORT").as_deref() {
+            Ok("quic") => Transport::Quic,
+            _ => Transport::Ws,
+        }
+    }
+}
+
+pub(crate) fn resolve_config_path() -> PathBuf {
+    // Common run path: project root: pip_--race/pit_timer_backend
+    // Config lives at src/../tracks/monaco.json or tracks/monaco.json at root
+    let candidates = [
+        PathBuf::from("src/tracks/monaco.json"),
+        PathBuf::from("tracks/monaco.json"),
+        PathBuf::from("./tracks/monaco.json"),
+        {
+            let mut p = std::env::current_exe().unwrap_or_else(|_| PathBuf::from("."));
+            p.pop(); // exe dir
+            p.push("tracks/monaco.json");
+            p
+        },
+    ];
+
+    for c in candidates {
+        if c.exists() {
+            return c;
+        }
+    }
+
+    // Fallback to default relative path; load() will error
+    PathBuf::from("tracks/monaco.json")
+}
@@ -0,0 +1,144 @@
+use quinn::{Endpoint, ServerConfig};
+use speed_profile_calculator::SpeedProfileCalculator;
+use tokio::io::AsyncWriteExt;
+use tokio::sync::broadcast;
+
+use super::{resolve_config_path, TimerOut};
+use crate::scheduler::CallScheduler;
+use crate::{config, model};
+
+/// QUIC transport: each `TelemetryPacket` arrives as an unreliable datagram,
+/// so a delayed packet is simply dropped rather than retransmitted and
+/// head-of-line-blocking everything behind it; `TimerOut` fan-out still goes
+/// out on a reliable uni stream per broadcast message.
+pub async fn serve(bind_addr: &str, tx: broadcast::Sender<TimerOut>) {
+    let addr: std::net::SocketAddr = match bind_addr.parse() {
+        Ok(a) => a,
+        Err(e) => {
+            eprintln!("Invalid QUIC bind address {}: {}", bind_addr, e);
+            return;
+        }
+    };
+
+    let server_config = match dev_server_config() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("Failed to build QUIC server config: {}", e);
+            return;
+        }
+    };
+
+    let endpoint = match Endpoint::server(server_config, addr) {
+        Ok(e) => e,
+        Err(e) => {
+            eprintln!("Failed to bind QUIC endpoint at {}: {}", addr, e);
+            return;
+        }
+    };
+    eprintln!("🚀 Rust backend listening on quic://{}", addr);
+
+    while let Some(incoming) = endpoint.accept().await {
+        let tx_clone = tx.clone();
+        tokio::spawn(async move {
+            match incoming.await {
+                Ok(conn) => handle_connection(conn, tx_clone).await,
+                Err(e) => eprintln!("QUIC handshake failed: {}", e),
+            }
+        });
+    }
+}
+
+async fn handle_connection(conn: quinn::Connection, tx: broadcast::Sender<TimerOut>) {
+    eprintln!("Client connected: {}", conn.remote_address());
+    let mut rx = tx.subscribe();
+
+    // Try to resolve config path robustly: prefer workspace-relative, then CWD.
+    let cfg_path = resolve_config_path();
+    let cfg = config::TimerConfig::load(cfg_path.to_str().unwrap());
+
+    // Writer task: forwards broadcast messages to this client on a fresh
+    // reliable uni stream per message (TimerOut is small and infrequent
+    // enough that per-message streams are simpler than a long-lived one).
+    let writer_conn = conn.clone();
+    let mut write_task = tokio::spawn(async move {
+        while let Ok(out) = rx.recv().await {
+            let Ok(text) = serde_json::to_string(&out) else { continue };
+            let Ok(mut send) = writer_conn.open_uni().await else { break };
+            if send.write_all(text.as_bytes()).await.is_err() {
+                break;
+            }
+            let _ = send.finish();
+        }
+    });
+
+    // Multi-point call schedule, built only when the track config actually
+    // defines `call_points`; otherwise this connection runs the legacy
+    // single-point `time_to_call` behavior below unchanged.
+    let mut scheduler = (!cfg.call_points.is_empty())
+        .then(|| CallScheduler::new(cfg.call_points.clone(), cfg.handoff));
+    let mut profiles = SpeedProfileCalculator::new(cfg.profile_window, cfg.profile_lookahead_m);
+    let mut last_lap_distance_m: Option<f64> = None;
+
+    // Reader loop: process incoming telemetry datagrams, compute, and broadcast.
+    loop {
+        match conn.read_datagram().await {
+            Ok(bytes) => {
+                if let Ok(data) = serde_json::from_slice::<model::TelemetryPacket>(&bytes) {
+                    let (t_call, t_safe, status) = model::time_to_call(&data, &cfg);
+
+                    // A drop in lap distance means a new lap started; reset
+                    // hand-off so the schedule starts back at the first
+                    // call point.
+                    if last_lap_distance_m.is_some_and(|prev| data.lap_distance_m < prev) {
+                        if let Some(scheduler) = scheduler.as_mut() {
+                            scheduler.reset();
+                        }
+                    }
+                    last_lap_distance_m = Some(data.lap_distance_m);
+
+                    profiles.add_sample(data.lap_distance_m, data.speed_kph);
+                    let (active_call, next_call) = match scheduler.as_mut() {
+                        Some(scheduler) => {
+                            let out = scheduler.update(data.lap_distance_m, data.speed_kph, &profiles);
+                            (out.active, out.next)
+                        }
+                        None => (None, None),
+                    };
+
+                    let out = TimerOut {
+                        t_call,
+                        t_safe,
+                        status,
+                        lap_distance_m: data.lap_distance_m,
+                        speed_kph: data.speed_kph,
+                        active_call,
+                        next_call,
+                    };
+
+                    println!(
+                        "lap={:.1}m speed={:.1}kph t_call={:.2}s t_safe={:.2}s status={}",
+                        out.lap_distance_m, out.speed_kph, out.t_call, out.t_safe, out.status
+                    );
+
+                    let _ = tx.send(out);
+                }
+            }
+            Err(_) => break, // connection closed or errored
+        }
+    }
+
+    write_task.abort();
+}
+
+/// Self-signed dev certificate so QUIC can terminate TLS without an
+/// operator-supplied cert; production deployments should load a real one.
+fn dev_server_config() -> Result<ServerConfig, Box<dyn std::error::Error>> {
+    let cert = rcgen::generate_simple_self_signed(vec!["localhost".into()])?;
+    let cert_der = cert.serialize_der()?;
+    let key_der = cert.serialize_private_key_der();
+
+    let cert_chain = vec![rustls::Certificate(cert_der)];
+    let key = rustls::PrivateKey(key_der);
+
+    Ok(ServerConfig::with_single_cert(cert_chain, key)?)
+}
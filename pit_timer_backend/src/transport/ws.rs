@@ -0,0 +1,224 @@
+use futures_util::{SinkExt, StreamExt};
+use serde::Serialize;
+use speed_profile_calculator::SpeedProfileCalculator;
+use std::os::unix::io::AsRawFd;
+use std::time::{Duration, Instant};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::broadcast;
+use tokio_tungstenite::accept_async;
+use tokio_tungstenite::tungstenite::{error::ProtocolError, Error as WsError};
+
+use super::health::{self, TcpHealth};
+use super::{resolve_config_path, TimerOut};
+use crate::scheduler::CallScheduler;
+use crate::{config, model};
+
+/// How much weight the latest send-latency sample gets in the EWMA; lower
+/// is smoother but slower to react to a client that's actually lagging.
+const SEND_LATENCY_EWMA_ALPHA: f64 = 0.2;
+/// EWMA time spent blocked in `write.send().await` above which a client is
+/// considered unable to keep up.
+const LAG_THRESHOLD_MS: f64 = 50.0;
+/// Below half the threshold we consider the client recovered and relax decimation.
+const RECOVER_THRESHOLD_MS: f64 = LAG_THRESHOLD_MS / 2.0;
+/// Round-trip time above which the link itself, not our send loop, is the
+/// likely cause of a lagging client.
+const RTT_LAG_THRESHOLD_MS: f64 = 80.0;
+const MAX_DECIMATION: u32 = 32;
+const STATUS_FRAME_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Periodic status frame reporting this connection's measured send latency,
+/// current decimation factor, and (where available) kernel-reported TCP
+/// health, interleaved with `TimerOut` messages.
+#[derive(Serialize, Debug, Clone)]
+struct StatusFrame {
+    kind: &'static str,
+    decimate_n: u32,
+    send_latency_ms_ewma: f64,
+    #[serde(flatten)]
+    tcp: Option<TcpHealth>,
+}
+
+pub async fn serve(bind_addr: &str, tx: broadcast::Sender<TimerOut>) {
+    let listener = match TcpListener::bind(bind_addr).await {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("Failed to bind TCP listener at {}: {}", bind_addr, e);
+            return;
+        }
+    };
+    eprintln!("🚀 Rust backend listening on ws://{}", bind_addr);
+
+    loop {
+        match listener.accept().await {
+            Ok((stream, addr)) => {
+                let tx_clone = tx.clone();
+                let rx = tx.subscribe();
+                tokio::spawn(handle_connection(stream, addr.to_string(), tx_clone, rx));
+            }
+            Err(e) => {
+                eprintln!("Accept error: {}", e);
+                // small delay to avoid tight loop in case of persistent errors
+                tokio::time::sleep(std::time::Duration::from_millis(200)).await;
+            }
+        }
+    }
+}
+
+async fn handle_connection(
+    stream: TcpStream,
+    peer_addr: String,
+    tx: broadcast::Sender<TimerOut>,
+    mut rx: broadcast::Receiver<TimerOut>,
+) {
+    health::configure_keepalive(&stream);
+    let raw_fd = stream.as_raw_fd();
+
+    let ws_stream = match accept_async(stream).await {
+        Ok(ws) => {
+            eprintln!("Client connected: {}", peer_addr);
+            ws
+        }
+        Err(WsError::Protocol(ProtocolError::HandshakeIncomplete)) => {
+            // Likely a TCP probe (e.g., healthcheck) that connected and closed without a WS handshake.
+            // Suppress noisy logging.
+            return;
+        }
+        Err(e) => {
+            eprintln!("WebSocket handshake failed from {}: {}", peer_addr, e);
+            return;
+        }
+    };
+    let (mut write, mut read) = ws_stream.split();
+
+    // Try to resolve config path robustly: prefer workspace-relative, then CWD.
+    let cfg_path = resolve_config_path();
+    let cfg = config::TimerConfig::load(cfg_path.to_str().unwrap());
+
+    // Writer task: forwards broadcast messages to this websocket client.
+    //
+    // Every client gets every `TimerOut` via `broadcast::Receiver`, so a
+    // slow client would otherwise silently lag or hit `RecvError::Lagged`.
+    // We measure how long `write.send(...).await` itself takes to complete
+    // as an EWMA — i.e. time actually blocked pushing bytes into a full
+    // socket buffer, not the gap since the last message (which is dominated
+    // by how often the upstream feed produces `TimerOut`s and would flag
+    // every client as lagging on a slow source). Once that exceeds
+    // `LAG_THRESHOLD_MS` we switch into decimated mode, forwarding only
+    // every Nth message (N grows with measured lag, shrinks as it recovers)
+    // so a lagging client gets a coherent, sparser stream instead of
+    // lag-resets.
+    let mut write_task = tokio::spawn(async move {
+        let mut seq: u64 = 0;
+        let mut decimate_n: u32 = 1;
+        let mut latency_ewma_ms = 0.0_f64;
+        let mut last_status = Instant::now();
+
+        while let Ok(out) = rx.recv().await {
+            seq += 1;
+            if seq % decimate_n as u64 != 0 {
+                continue;
+            }
+
+            let Ok(text) = serde_json::to_string(&out) else { continue };
+            let send_start = Instant::now();
+            if write.send(tokio_tungstenite::tungstenite::Message::Text(text)).await.is_err() {
+                break;
+            }
+            let elapsed_ms = send_start.elapsed().as_secs_f64() * 1000.0;
+            latency_ewma_ms = SEND_LATENCY_EWMA_ALPHA * elapsed_ms + (1.0 - SEND_LATENCY_EWMA_ALPHA) * latency_ewma_ms;
+
+            let due_for_status = last_status.elapsed() >= STATUS_FRAME_INTERVAL;
+            let tcp_health = if due_for_status { health::read_tcp_info(raw_fd) } else { None };
+            let rtt_lagging = tcp_health.is_some_and(|h| h.rtt_ms > RTT_LAG_THRESHOLD_MS);
+
+            if latency_ewma_ms > LAG_THRESHOLD_MS || rtt_lagging {
+                decimate_n = (decimate_n + 1).min(MAX_DECIMATION);
+            } else if latency_ewma_ms < RECOVER_THRESHOLD_MS {
+                decimate_n = (decimate_n.saturating_sub(1)).max(1);
+            }
+
+            if due_for_status {
+                if let Some(h) = tcp_health {
+                    println!(
+                        "peer={} rtt_ms={:.2} rtt_var_ms={:.2} retransmits={} decimate_n={}",
+                        peer_addr, h.rtt_ms, h.rtt_var_ms, h.retransmits, decimate_n
+                    );
+                }
+
+                let status = StatusFrame {
+                    kind: "health",
+                    decimate_n,
+                    send_latency_ms_ewma: latency_ewma_ms,
+                    tcp: tcp_health,
+                };
+                if let Ok(text) = serde_json::to_string(&status) {
+                    if write.send(tokio_tungstenite::tungstenite::Message::Text(text)).await.is_err() {
+                        break;
+                    }
+                }
+                last_status = Instant::now();
+            }
+        }
+    });
+
+    // Multi-point call schedule, built only when the track config actually
+    // defines `call_points`; otherwise this connection runs the legacy
+    // single-point `time_to_call` behavior below unchanged.
+    let mut scheduler = (!cfg.call_points.is_empty())
+        .then(|| CallScheduler::new(cfg.call_points.clone(), cfg.handoff));
+    let mut profiles = SpeedProfileCalculator::new(cfg.profile_window, cfg.profile_lookahead_m);
+    let mut last_lap_distance_m: Option<f64> = None;
+
+    // Reader loop: process incoming telemetry, compute, and broadcast
+    while let Some(msg) = read.next().await {
+        if let Ok(msg) = msg {
+            if msg.is_text() {
+                if let Ok(data) = serde_json::from_str::<model::TelemetryPacket>(&msg.to_string()) {
+                    let (t_call, t_safe, status) = model::time_to_call(&data, &cfg);
+
+                    // A drop in lap distance means a new lap started; reset
+                    // hand-off so the schedule starts back at the first
+                    // call point.
+                    if last_lap_distance_m.is_some_and(|prev| data.lap_distance_m < prev) {
+                        if let Some(scheduler) = scheduler.as_mut() {
+                            scheduler.reset();
+                        }
+                    }
+                    last_lap_distance_m = Some(data.lap_distance_m);
+
+                    profiles.add_sample(data.lap_distance_m, data.speed_kph);
+                    let (active_call, next_call) = match scheduler.as_mut() {
+                        Some(scheduler) => {
+                            let out = scheduler.update(data.lap_distance_m, data.speed_kph, &profiles);
+                            (out.active, out.next)
+                        }
+                        None => (None, None),
+                    };
+
+                    let out = TimerOut {
+                        t_call,
+                        t_safe,
+                        status,
+                        lap_distance_m: data.lap_distance_m,
+                        speed_kph: data.speed_kph,
+                        active_call,
+                        next_call,
+                    };
+
+                    // Log a compact line for observability
+                    println!(
+                        "lap={:.1}m speed={:.1}kph t_call={:.2}s t_safe={:.2}s status={}",
+                        out.lap_distance_m, out.speed_kph, out.t_call, out.t_safe, out.status
+                    );
+
+                    // Ignore send errors (no subscribers)
+                    let _ = tx.send(out);
+                }
+            }
+        }
+    }
+
+    // Ensure writer task stops when reader ends
+    write_task.abort();
+}
@@ -0,0 +1,159 @@
+use serde::Serialize;
+
+use rt_predictor::types::PredictionOut;
+
+/// Probability buckets for the reliability diagram: `[0.0,0.1), ... ,[0.9,1.0]`.
+const NUM_BUCKETS: usize = 10;
+
+/// One bucket of the reliability diagram: how many predictions landed in
+/// this probability range, their mean predicted probability, and the
+/// fraction that actually boxed within the horizon. A well-calibrated model
+/// has `mean_predicted` close to `observed_frequency` in every bucket.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReliabilityBucket {
+    pub lo: f32,
+    pub hi: f32,
+    pub count: usize,
+    pub mean_predicted: f32,
+    pub observed_frequency: f32,
+}
+
+/// Calibration quality for one box-probability horizon (2-lap or 3-lap).
+#[derive(Debug, Clone, Serialize)]
+pub struct HorizonReport {
+    /// Mean of `(predicted - outcome)^2` over every scored prediction.
+    pub brier_score: f32,
+    pub n: usize,
+    pub buckets: Vec<ReliabilityBucket>,
+}
+
+#[derive(Debug, Default)]
+struct HorizonAccumulator {
+    sum_sq_err: f64,
+    n: usize,
+    bucket_sum_pred: [f64; NUM_BUCKETS],
+    bucket_sum_outcome: [f64; NUM_BUCKETS],
+    bucket_count: [usize; NUM_BUCKETS],
+}
+
+impl HorizonAccumulator {
+    fn observe(&mut self, predicted: f32, outcome: bool) {
+        let outcome_f = if outcome { 1.0_f64 } else { 0.0_f64 };
+        let err = predicted as f64 - outcome_f;
+        self.sum_sq_err += err * err;
+        self.n += 1;
+
+        let bucket = ((predicted.clamp(0.0, 1.0) * NUM_BUCKETS as f32) as usize).min(NUM_BUCKETS - 1);
+        self.bucket_sum_pred[bucket] += predicted as f64;
+        self.bucket_sum_outcome[bucket] += outcome_f;
+        self.bucket_count[bucket] += 1;
+    }
+
+    fn report(&self) -> HorizonReport {
+        let brier_score = if self.n == 0 {
+            0.0
+        } else {
+            (self.sum_sq_err / self.n as f64) as f32
+        };
+
+        let buckets = (0..NUM_BUCKETS)
+            .map(|i| {
+                let count = self.bucket_count[i];
+                let mean_predicted = if count == 0 {
+                    0.0
+                } else {
+                    (self.bucket_sum_pred[i] / count as f64) as f32
+                };
+                let observed_frequency = if count == 0 {
+                    0.0
+                } else {
+                    (self.bucket_sum_outcome[i] / count as f64) as f32
+                };
+                ReliabilityBucket {
+                    lo: i as f32 / NUM_BUCKETS as f32,
+                    hi: (i + 1) as f32 / NUM_BUCKETS as f32,
+                    count,
+                    mean_predicted,
+                    observed_frequency,
+                }
+            })
+            .collect();
+
+        HorizonReport { brier_score, n: self.n, buckets }
+    }
+}
+
+/// Running calibration quality for both box-probability horizons, serialized
+/// as one report so users get a concrete way to validate that the
+/// probabilities this crate emits actually mean what they say.
+#[derive(Debug, Clone, Serialize)]
+pub struct CalibrationReport {
+    pub within2: HorizonReport,
+    pub within3: HorizonReport,
+}
+
+/// Joins each emitted [`PredictionOut`] to the pit outcome eventually
+/// observed within its 2- and 3-lap horizon, and tracks running Brier score
+/// and reliability-diagram statistics for both.
+#[derive(Debug, Default)]
+pub struct Calibrator {
+    within2: HorizonAccumulator,
+    within3: HorizonAccumulator,
+}
+
+impl Calibrator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Score `pred` against the ground truth: `actual_box_lap` is the lap
+    /// the driver actually pitted on, or `None` if they didn't box at all
+    /// in the window the caller is scoring over.
+    pub fn observe(&mut self, pred: &PredictionOut, actual_box_lap: Option<i32>) {
+        let boxed_within = |horizon: i32| {
+            actual_box_lap.is_some_and(|box_lap| box_lap > pred.lap && box_lap <= pred.lap + horizon)
+        };
+
+        self.within2.observe(pred.prob_box_within2, boxed_within(2));
+        self.within3.observe(pred.prob_box_within3, boxed_within(3));
+    }
+
+    /// Snapshot the running metrics into a serializable report.
+    pub fn report(&self) -> CalibrationReport {
+        CalibrationReport {
+            within2: self.within2.report(),
+            within3: self.within3.report(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pred(lap: i32, prob: f32) -> PredictionOut {
+        PredictionOut { driver: "VER".to_string(), lap, prob_box_within2: prob, prob_box_within3: prob, ts_ms: 0 }
+    }
+
+    #[test]
+    fn perfectly_confident_correct_predictions_score_zero_brier() {
+        let mut cal = Calibrator::new();
+        cal.observe(&pred(1, 1.0), Some(2));
+        cal.observe(&pred(5, 0.0), None);
+
+        let report = cal.report();
+        assert_eq!(report.within2.brier_score, 0.0);
+        assert_eq!(report.within2.n, 2);
+    }
+
+    #[test]
+    fn box_outside_the_horizon_counts_as_a_miss() {
+        let mut cal = Calibrator::new();
+        // Predicted high confidence of boxing within 2 laps, but the driver
+        // didn't box until lap offset 3 - outside the within2 horizon.
+        cal.observe(&pred(10, 1.0), Some(13));
+
+        let report = cal.report();
+        assert!(report.within2.brier_score > 0.0);
+    }
+}
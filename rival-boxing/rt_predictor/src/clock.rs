@@ -0,0 +1,31 @@
+/// Host-supplied source of wall-clock time for `ts_ms` fields. The
+/// predictor's core types and math never call into the system clock
+/// directly — on a `no_std` embedded pit-wall dashboard there may not be
+/// one — so any code that needs to stamp a [`crate::types::PredictionOut`]
+/// takes a `&dyn Clock` and the host registers whichever implementation
+/// fits, the same pluggable-registration shape as the `rt_predictor`
+/// binary's `InferenceModule` pipeline for the inference path itself.
+pub trait Clock {
+    /// Milliseconds since the Unix epoch, or any host-chosen origin — this
+    /// crate only ever compares `ts_ms` values, never parses them as a
+    /// calendar date.
+    fn now_ms(&self) -> i64;
+}
+
+/// [`Clock`] backed by `std::time::SystemTime`, the default wherever an OS
+/// clock is available. Not available under `no_std`; a bare-metal host
+/// implements [`Clock`] itself against whatever timer hardware it has.
+#[cfg(feature = "std")]
+#[derive(Debug, Default, Clone, Copy)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now_ms(&self) -> i64 {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis() as i64
+    }
+}
@@ -0,0 +1,66 @@
+use std::io::{BufRead, Write};
+
+use rt_predictor::types::{PredictionOut, TelemetryPacket};
+
+/// Read newline-delimited `TelemetryPacket` JSON from `input`, run each
+/// through `predict`, and write any `Some(PredictionOut)` back to `out` as
+/// newline-delimited JSON, flushed immediately so a downstream consumer
+/// (a race dashboard or Python feeder piping this in as a subprocess) sees
+/// per-lap latency instead of buffered batches.
+///
+/// Malformed lines are skipped rather than aborting the stream; the number
+/// skipped is returned so a caller can log or alert on a noisy feed.
+pub fn run_stream<R, W>(
+    input: R,
+    mut out: W,
+    mut predict: impl FnMut(&TelemetryPacket) -> Option<PredictionOut>,
+) -> usize
+where
+    R: BufRead,
+    W: Write,
+{
+    let mut skipped = 0usize;
+
+    for line in input.lines() {
+        let line = match line {
+            Ok(l) => l,
+            Err(e) => {
+                eprintln!("skipping unreadable input line: {}", e);
+                skipped += 1;
+                continue;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let packet: TelemetryPacket = match serde_json::from_str(&line) {
+            Ok(p) => p,
+            Err(e) => {
+                eprintln!("skipping malformed telemetry line: {}", e);
+                skipped += 1;
+                continue;
+            }
+        };
+
+        let Some(prediction) = predict(&packet) else { continue };
+        let Ok(text) = serde_json::to_string(&prediction) else { continue };
+        if writeln!(out, "{}", text).is_err() {
+            break;
+        }
+        if out.flush().is_err() {
+            break;
+        }
+    }
+
+    skipped
+}
+
+/// Convenience wrapper over [`run_stream`] wired to process stdin/stdout, so
+/// the predictor can run embedded as a subprocess instead of behind HTTP.
+/// Returns the number of malformed input lines skipped.
+pub fn run_stdio(predict: impl FnMut(&TelemetryPacket) -> Option<PredictionOut>) -> usize {
+    let stdin = std::io::stdin();
+    let stdout = std::io::stdout();
+    run_stream(stdin.lock(), stdout.lock(), predict)
+}
@@ -0,0 +1,18 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+//! The `no_std`-capable core of the predictor: the telemetry/prediction
+//! types, the injectable wall-clock trait, and the pure probability math
+//! (sigmoid, CVaR-over-quantiles). None of it touches the OS clock, a
+//! filesystem, or JSON, so with the `std`/`serde` features off this crate
+//! builds on a bare-metal pit-wall dashboard with no OS — though it still
+//! needs `alloc` for the `String` fields on `TelemetryPacket`/`PredictionOut`.
+//!
+//! Everything that *does* need `std` — the axum/tokio HTTP server, the
+//! ONNX/TorchScript model backends, `meta.json` loading, metrics, and the
+//! stdio JSON pipeline — lives in the `rt_predictor` binary (`main.rs` and
+//! its modules) instead, which depends on this crate rather than the other
+//! way around.
+
+pub mod clock;
+pub mod math;
+pub mod types;
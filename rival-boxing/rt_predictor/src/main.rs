@@ -1,15 +1,26 @@
 use axum::{routing::post, extract::State, Json, http::StatusCode};
 use serde::Deserialize;
 use serde_json::json;
-use std::{collections::HashMap, sync::Arc, time::{SystemTime, UNIX_EPOCH}};
+use std::{collections::HashMap, sync::Arc};
 
+mod calibration;
+mod io;
 mod model;
+mod modules;
+mod race_plan;
+mod stint;
+mod strategy;
+
+use model::PitModel;
+use modules::InferenceModule;
+use rt_predictor::clock::{Clock, SystemClock};
+use rt_predictor::types::{self, Compound};
 
 // ---------- Request/Response types ----------
 
 // FLAT request: driver, lap, PLUS all features as top-level keys
 #[derive(Deserialize, Debug)]
-struct IngestFlat {
+pub struct IngestFlat {
     driver: String,
     lap: i32,
     #[serde(flatten)]
@@ -18,7 +29,7 @@ struct IngestFlat {
 
 // Response: only p2 and p3 as requested
 #[derive(serde::Serialize)]
-struct Out {
+pub struct Out {
     t: i64,
     driver: String,
     lap: i32,
@@ -30,28 +41,117 @@ struct Out {
 
 #[derive(Clone)]
 struct AppState {
-    mdl: Arc<model::Model>,
-    feat_list: Arc<Vec<String>>, // authoritative input order
+    mdl: Arc<dyn PitModel + Send + Sync>,
+    modules: Arc<Vec<Box<dyn InferenceModule + Send + Sync>>>,
+    clock: Arc<dyn Clock + Send + Sync>,
 }
 
 // ---------- Feature ordering utility ----------
 
+/// Missing keys become `f32::NAN` rather than `0.0` so downstream
+/// `InferenceModule`s (see [`modules`]) can tell "absent" apart from
+/// "present and zero"; the built-in [`modules::ZeroFillMissing`] module
+/// restores the historical zero-fill default.
 fn order_from_flat(map: &HashMap<String, f32>, feat_list: &[String]) -> Vec<f32> {
     let mut v = Vec::with_capacity(feat_list.len());
     for k in feat_list {
-        v.push(*map.get(k).unwrap_or(&0.0));
+        v.push(*map.get(k).unwrap_or(&f32::NAN));
     }
     v
 }
 
+fn module_err(e: anyhow::Error) -> (StatusCode, Json<serde_json::Value>) {
+    (StatusCode::BAD_REQUEST, Json(json!({ "error": e.to_string() })))
+}
+
+/// Flatten a structured [`types::TelemetryPacket`] into the same
+/// name -> value map the HTTP `/ingest` path takes, so the stdio pipeline
+/// (see [`io::run_stdio`]) can reuse [`order_from_flat`] instead of its own
+/// feature-ordering logic.
+fn packet_to_feature_map(p: &types::TelemetryPacket) -> HashMap<String, f32> {
+    let mut m = HashMap::new();
+    m.insert("lap".to_string(), p.lap as f32);
+    if let Some(v) = p.tyre_laps {
+        m.insert("tyre_laps".to_string(), v as f32);
+    }
+    if let Some(v) = p.gap_front {
+        m.insert("gap_front".to_string(), v);
+    }
+    if let Some(v) = p.track_status_code {
+        m.insert("track_status_code".to_string(), v as i32 as f32);
+    }
+    if let Some(v) = p.pit_window_lap {
+        m.insert("pit_window_lap".to_string(), v as f32);
+    }
+    if let Some(c) = p.compound {
+        m.insert("compound_degradation_rank".to_string(), c.degradation_rank() as f32);
+        m.insert("compound_is_slick".to_string(), c.is_slick() as i32 as f32);
+    }
+    m
+}
+
+// ---------- Strategy planning ----------
+
+/// Wire format for `/plan_strategy`: a [`race_plan::RaceState`] plus a
+/// [`race_plan::StrategyConfig`] and the caller's own pace curve, flattened
+/// into one JSON body since neither type derives `Deserialize` on its own
+/// (their fields aren't optional the way a raw telemetry packet's are).
+#[derive(Deserialize, Debug)]
+struct PlanStrategyRequest {
+    lap: i32,
+    compound: Compound,
+    tyre_laps: i32,
+    gap_front: f32,
+    race_laps: i32,
+    pit_loss_s: f32,
+    candidate_compounds: Vec<Compound>,
+    /// `penalties_s[compound.degradation_rank() - 1][tyre_age]`, seconds
+    /// lost versus a fresh tyre; see [`race_plan::TablePaceModel`].
+    penalties_s: Vec<Vec<f32>>,
+    #[serde(default = "default_top_k")]
+    top_k: usize,
+}
+
+fn default_top_k() -> usize {
+    3
+}
+
+async fn plan_strategy(
+    Json(req): Json<PlanStrategyRequest>,
+) -> Result<Json<Vec<race_plan::StrategyPlan>>, (StatusCode, Json<serde_json::Value>)> {
+    let state = race_plan::RaceState {
+        lap: req.lap,
+        compound: req.compound,
+        tyre_laps: req.tyre_laps,
+        gap_front: req.gap_front,
+    };
+    let cfg = race_plan::StrategyConfig {
+        race_laps: req.race_laps,
+        pit_loss_s: req.pit_loss_s,
+        candidate_compounds: req.candidate_compounds,
+    };
+    let model = race_plan::TablePaceModel { penalties_s: req.penalties_s };
+
+    race_plan::plan_strategy(&state, &model, &cfg, req.top_k)
+        .map(Json)
+        .map_err(|e| (StatusCode::BAD_REQUEST, Json(json!({ "error": e.to_string() }))))
+}
+
 // ---------- Handler ----------
 
 async fn ingest(
     State(state): State<AppState>,
-    Json(payload): Json<IngestFlat>,
+    Json(mut payload): Json<IngestFlat>,
 ) -> Result<Json<Out>, (StatusCode, Json<serde_json::Value>)> {
+    for m in state.modules.iter() {
+        m.on_request(&mut payload).map_err(module_err)?;
+    }
+
     // Map incoming flat map -> ordered vector
-    let vec = order_from_flat(&payload.features, &state.feat_list);
+    let mut vec = order_from_flat(&payload.features, state.mdl.feat_list());
+    for m in state.modules.iter() {
+        m.on_features(&mut vec, state.mdl.feat_list()).map_err(module_err)?;
+    }
 
     // Debug signal so we can confirm we're not sending all-zeros
     if std::env::var("LOG_PRED").ok().as_deref() == Some("1") {
@@ -64,7 +164,7 @@ async fn ingest(
             (vec.iter().map(|x| (x - m) * (x - m)).sum::<f32>() / (vec.len() as f32)).sqrt()
         };
         let mut sample = vec![];
-        for (i, name) in state.feat_list.iter().take(6).enumerate() {
+        for (i, name) in state.mdl.feat_list().iter().take(6).enumerate() {
             sample.push(format!("{}={:.3}", name, vec[i]));
         }
         tracing::info!(
@@ -76,45 +176,197 @@ async fn ingest(
     // Run inference (returns p2/p3)
     let (p2, p3) = state
         .mdl
-        .predict_probs(&vec, state.feat_list.len())
+        .predict_probs(&vec)
         .map_err(|e| (StatusCode::INTERNAL_SERVER_ERROR, Json(json!({ "error": e.to_string() }))))?;
 
-    let now_ms = SystemTime::now().duration_since(UNIX_EPOCH).unwrap().as_millis() as i64;
-    Ok(Json(Out {
-        t: now_ms,
+    let mut out = Out {
+        t: state.clock.now_ms(),
         driver: payload.driver,
         lap: payload.lap,
         p2,
         p3,
-    }))
+    };
+    for m in state.modules.iter() {
+        m.on_response(&mut out).map_err(module_err)?;
+    }
+
+    Ok(Json(out))
 }
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
     tracing_subscriber::fmt::init();
 
+    // Installs the global `metrics` recorder so `model::record_forward`'s
+    // `counter!`/`histogram!` calls (currently a no-op without this) are
+    // actually scrapeable, in both HTTP and stdio modes; the handle is
+    // mounted at `/metrics` below for HTTP mode.
+    let metrics_handle = metrics_exporter_prometheus::PrometheusBuilder::new()
+        .install_recorder()
+        .map_err(|e| anyhow::anyhow!("failed to install metrics recorder: {}", e))?;
+
     let model_path = std::env::var("MODEL_PATH").expect("MODEL_PATH not set");
     let meta_path  = std::env::var("META_PATH").expect("META_PATH not set");
     let port: u16  = std::env::var("PORT").ok().and_then(|s| s.parse().ok()).unwrap_or(8080);
 
-    let (mdl, in_dim, feat_list) = model::Model::new(&model_path, &meta_path)?;
-    if in_dim != feat_list.len() {
-        tracing::warn!("meta.in_dim ({}) != feat_list.len() ({}); using feat_list.len()", in_dim, feat_list.len());
+    let mdl = model::load_model(&model_path, &meta_path)?;
+    if mdl.in_dim() != mdl.feat_list().len() {
+        tracing::warn!(
+            "meta.in_dim ({}) != feat_list.len() ({}); using feat_list.len()",
+            mdl.in_dim(),
+            mdl.feat_list().len()
+        );
     }
-    // Warmup to ensure JIT is happy
-    let _ = mdl.predict_probs(&vec![0.0; feat_list.len()], feat_list.len())?;
+    // Warmup to ensure the backend is happy
+    let _ = mdl.predict_probs(&vec![0.0; mdl.feat_list().len()])?;
     tracing::info!("warmup forward ok");
 
-    tracing::info!("loaded model; feat_list[{}]: {:?}", feat_list.len(), &feat_list);
+    tracing::info!(
+        "loaded model version={} feat_list[{}]: {:?}",
+        mdl.model_version(),
+        mdl.feat_list().len(),
+        mdl.feat_list()
+    );
 
-    let state = AppState {
-        mdl: Arc::new(mdl),
-        feat_list: Arc::new(feat_list),
-    };
+    let mdl: Arc<dyn PitModel + Send + Sync> = Arc::from(mdl);
+    let clock: Arc<dyn Clock + Send + Sync> = Arc::new(SystemClock);
+
+    // Embedding mode: newline-delimited TelemetryPacket/PredictionOut over
+    // stdin/stdout instead of HTTP, for running as a subprocess behind a
+    // race dashboard or Python feeder.
+    if std::env::var("MODE").as_deref() == Ok("stdio") {
+        let predict_mdl = mdl.clone();
+        let predict_clock = clock.clone();
+        let predict_modules: Vec<Box<dyn InferenceModule + Send + Sync>> =
+            vec![Box::new(modules::ZeroFillMissing)];
+
+        // Per-driver stint stats, folded live from the same packet/prediction
+        // stream the predictions come from; dumped as a JSON report once the
+        // stream ends if `STINT_REPORT_PATH` is set, so a strategist gets an
+        // auditable post-session summary instead of only the per-lap feed.
+        let stint_threshold: f32 = std::env::var("STINT_PROB_THRESHOLD")
+            .ok()
+            .and_then(|s| s.parse().ok())
+            .unwrap_or(0.5);
+        let stint_agg = std::rc::Rc::new(std::cell::RefCell::new(stint::StintAggregator::new(stint_threshold)));
+        let stint_agg_for_predict = stint_agg.clone();
+
+        // Online calibration against the stream's own ground truth: each
+        // prediction sits in `pending` (keyed by driver) until it either
+        // resolves (the driver boxes, within `CALIBRATION_HORIZON_LAPS` of
+        // the prediction) or ages out (it didn't), at which point it's
+        // scored into `calibrator`. Dumped as a report if
+        // `CALIBRATION_REPORT_PATH` is set, so Brier score / reliability
+        // stats are observable from a live run instead of only computable
+        // offline.
+        const CALIBRATION_HORIZON_LAPS: i32 = 3;
+        let calibrator = std::rc::Rc::new(std::cell::RefCell::new(calibration::Calibrator::new()));
+        let calibrator_for_predict = calibrator.clone();
+        let pending: std::rc::Rc<std::cell::RefCell<HashMap<String, std::collections::VecDeque<types::PredictionOut>>>> =
+            std::rc::Rc::new(std::cell::RefCell::new(HashMap::new()));
+        let pending_for_predict = pending.clone();
+
+        let predict = move |packet: &types::TelemetryPacket| -> Option<types::PredictionOut> {
+            let map = packet_to_feature_map(packet);
+            let mut vec = order_from_flat(&map, predict_mdl.feat_list());
+            for m in &predict_modules {
+                m.on_features(&mut vec, predict_mdl.feat_list()).ok()?;
+            }
+            let (p2, p3) = predict_mdl.predict_probs(&vec).ok()?;
+            let pred = types::PredictionOut {
+                driver: packet.driver.clone(),
+                lap: packet.lap,
+                prob_box_within2: p2,
+                prob_box_within3: p3,
+                ts_ms: predict_clock.now_ms(),
+            };
+            stint_agg_for_predict.borrow_mut().push(packet, &pred);
+
+            let mut pending = pending_for_predict.borrow_mut();
+            let mut calibrator = calibrator_for_predict.borrow_mut();
+            if packet.pitted_this_lap.unwrap_or(false) {
+                if let Some(queue) = pending.get_mut(&packet.driver) {
+                    for buffered in queue.drain(..) {
+                        calibrator.observe(&buffered, Some(packet.lap));
+                    }
+                }
+            }
+            let queue = pending.entry(packet.driver.clone()).or_default();
+            while let Some(front) = queue.front() {
+                if packet.lap - front.lap > CALIBRATION_HORIZON_LAPS {
+                    let expired = queue.pop_front().unwrap();
+                    calibrator.observe(&expired, None);
+                } else {
+                    break;
+                }
+            }
+            queue.push_back(pred.clone());
+
+            Some(pred)
+        };
+
+        let skipped = io::run_stdio(predict);
+        if skipped > 0 {
+            tracing::warn!("stdio stream finished, skipped {} malformed lines", skipped);
+        }
+        tracing::info!("stdio stream finished, served {} model forward passes", model::forward_count());
+
+        if let Ok(path) = std::env::var("CALIBRATION_REPORT_PATH") {
+            {
+                let mut pending = pending.borrow_mut();
+                let mut calibrator = calibrator.borrow_mut();
+                for queue in pending.values_mut() {
+                    for buffered in queue.drain(..) {
+                        calibrator.observe(&buffered, None);
+                    }
+                }
+            }
+            match serde_json::to_string_pretty(&calibrator.borrow().report()) {
+                Ok(text) => {
+                    if let Err(e) = std::fs::write(&path, text) {
+                        tracing::warn!("failed to write calibration report to {}: {}", path, e);
+                    }
+                }
+                Err(e) => tracing::warn!("failed to serialize calibration report: {}", e),
+            }
+        }
+
+        if let Ok(path) = std::env::var("STINT_REPORT_PATH") {
+            let stints = match std::rc::Rc::try_unwrap(stint_agg) {
+                Ok(cell) => cell.into_inner().finish(),
+                Err(_) => Vec::new(),
+            };
+            match serde_json::to_string_pretty(&stints) {
+                Ok(text) => {
+                    if let Err(e) = std::fs::write(&path, text) {
+                        tracing::warn!("failed to write stint report to {}: {}", path, e);
+                    }
+                }
+                Err(e) => tracing::warn!("failed to serialize stint report: {}", e),
+            }
+        }
+
+        return Ok(());
+    }
+
+    let modules: Vec<Box<dyn InferenceModule + Send + Sync>> = vec![Box::new(modules::ZeroFillMissing)];
+    let state = AppState { mdl, modules: Arc::new(modules), clock };
 
     let app = axum::Router::new()
         .route("/ingest", post(ingest))
-        .with_state(state);
+        .with_state(state)
+        .route("/plan_strategy", post(plan_strategy))
+        .route(
+            "/metrics",
+            axum::routing::get(move || {
+                let metrics_handle = metrics_handle.clone();
+                async move { metrics_handle.render() }
+            }),
+        )
+        .route(
+            "/stats",
+            axum::routing::get(|| async { Json(json!({ "forward_count": model::forward_count() })) }),
+        );
 
     let addr = std::net::SocketAddr::from(([0, 0, 0, 0], port));
     tracing::info!("listening on {}", addr);
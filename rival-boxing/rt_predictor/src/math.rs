@@ -0,0 +1,113 @@
+//! Pure probability math shared by every `PitModel` backend — no `std`, no
+//! allocation, just floats, so it's part of the `no_std` core (see the
+//! crate-level docs).
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+/// `f32::exp` is a `std`-only inherent method (it isn't in `core`), so the
+/// `no_std` build takes the exponential from `libm` instead.
+pub fn sigmoid(x: f32) -> f32 {
+    #[cfg(feature = "std")]
+    let exp_neg_x = (-x).exp();
+    #[cfg(not(feature = "std"))]
+    let exp_neg_x = libm::expf(-x);
+
+    1.0 / (1.0 + exp_neg_x)
+}
+
+/// `f64::floor` is likewise a `std`-only inherent method, so `no_std` takes
+/// it from `libm` too.
+fn floor(x: f64) -> f64 {
+    #[cfg(feature = "std")]
+    {
+        x.floor()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        libm::floor(x)
+    }
+}
+
+/// Lower-tail CVaR at `alpha` over quantile estimates `values`, assumed
+/// sorted ascending with entry `i` representing the return distribution's
+/// quantile at the midpoint `τ_i = (i+0.5)/Q` (`Q = values.len()`). The
+/// result is the mean of every quantile whose `τ_i <= alpha`, linearly
+/// interpolating the one quantile straddling `alpha` so the average stays
+/// continuous as `alpha * Q` crosses each integer (it need not be integral).
+pub fn cvar_from_quantiles(values: &[f32], alpha: f64) -> f32 {
+    let q = values.len();
+    if q == 0 {
+        return 0.0;
+    }
+    let alpha = alpha.clamp(0.0, 1.0);
+
+    // `count` is how many quantiles (possibly fractional) fall at or below
+    // `alpha` under the midpoint convention: `τ_i <= alpha` solves to
+    // `i <= alpha*Q - 0.5`, i.e. the first `floor(count)` entries are fully
+    // included and the next one is included to degree `frac`.
+    let count = (alpha * q as f64 + 0.5).min(q as f64);
+    let count_floor = floor(count);
+    let n_full = count_floor as usize;
+    let frac = count - count_floor;
+
+    let mut weighted_sum: f64 = values[..n_full].iter().map(|&v| v as f64).sum();
+    let mut weight = n_full as f64;
+    if frac > 0.0 {
+        if let Some(&v) = values.get(n_full) {
+            weighted_sum += v as f64 * frac;
+            weight += frac;
+        }
+    }
+
+    if weight <= 0.0 {
+        values[0]
+    } else {
+        (weighted_sum / weight) as f32
+    }
+}
+
+/// Risk-sensitive pit/no-pit comparison over the full return distribution.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct PitDecision {
+    pub alpha: f64,
+    pub cvar_no_pit: f32,
+    pub cvar_pit: f32,
+    pub mean_no_pit: f32,
+    pub mean_pit: f32,
+    /// `true` iff `CVaR_alpha(pit) > CVaR_alpha(no_pit)`.
+    pub recommend_pit: bool,
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn alpha_one_reduces_to_the_mean() {
+        let values = [1.0, 2.0, 3.0, 4.0];
+        let cvar = cvar_from_quantiles(&values, 1.0);
+        assert!((cvar - 2.5).abs() < 1e-6);
+    }
+
+    #[test]
+    fn boundary_quantile_at_exact_midpoint_is_fully_included() {
+        // Q=2 midpoints are tau_0=0.25, tau_1=0.75; alpha=0.75 exactly
+        // reaches tau_1, so both quantiles are fully included.
+        let values = [1.0, 3.0];
+        let cvar = cvar_from_quantiles(&values, 0.75);
+        assert!((cvar - 2.0).abs() < 1e-6);
+    }
+
+    #[test]
+    fn straddling_alpha_interpolates_the_boundary_quantile() {
+        // alpha=0.6, Q=2: tau_0=0.25 fully included, tau_1=0.75 is beyond
+        // alpha so only interpolated in proportion to how far alpha*Q+0.5
+        // reaches past the first full quantile.
+        let values = [1.0, 3.0];
+        let cvar = cvar_from_quantiles(&values, 0.6);
+        let expected = (1.0 * 1.0 + 3.0 * 0.7) / 1.7;
+        assert!((cvar - expected as f32).abs() < 1e-6);
+    }
+}
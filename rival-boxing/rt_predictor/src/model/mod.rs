@@ -0,0 +1,128 @@
+#[cfg(feature = "std")]
+mod onnx;
+#[cfg(feature = "std")]
+mod torchscript;
+
+#[cfg(feature = "std")]
+pub use onnx::OnnxModel;
+#[cfg(feature = "std")]
+pub use torchscript::TorchScriptModel;
+
+use anyhow::Result;
+
+use rt_predictor::math::{cvar_from_quantiles, sigmoid, PitDecision};
+
+// Model loading, hashing and file-backed `meta.json` parsing are all part of
+// the std/serde IO surface the `rt_predictor` lib crate's `no_std` core
+// (see `rt_predictor::math`, `rt_predictor::clock`) is split away from —
+// none of it makes sense on a `no_std` host with no filesystem.
+#[cfg(feature = "std")]
+mod host {
+    use anyhow::{Context, Result};
+    use serde::Deserialize;
+    use sha2::{Digest, Sha256};
+    use std::{
+        fs,
+        path::Path,
+        sync::atomic::{AtomicU64, Ordering},
+        time::Instant,
+    };
+
+    #[derive(Deserialize)]
+    pub(crate) struct MetaJson {
+        pub(crate) feat_list: Vec<String>,
+        pub(crate) in_dim: Option<usize>,
+        #[serde(default)]
+        pub(crate) backend: Backend,
+    }
+
+    /// Which `PitModel` implementation `meta.json` names for a deployed policy.
+    #[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+    #[serde(rename_all = "snake_case")]
+    pub enum Backend {
+        #[default]
+        Torchscript,
+        Onnx,
+    }
+
+    /// Read and parse `meta.json`, resolving `in_dim` from the explicit field or
+    /// `feat_list.len()`. Shared by every `PitModel` impl so the validation
+    /// doesn't drift between backends.
+    pub(crate) fn load_meta(meta_path: &str) -> Result<(MetaJson, usize)> {
+        let meta_txt = fs::read_to_string(Path::new(meta_path))
+            .with_context(|| format!("failed to read meta at {}", meta_path))?;
+        let meta: MetaJson =
+            serde_json::from_str(&meta_txt).with_context(|| "failed to parse meta.json")?;
+        let in_dim = meta.in_dim.unwrap_or(meta.feat_list.len());
+        Ok((meta, in_dim))
+    }
+
+    /// SHA-256 of the raw model file, so deployments can log and assert exactly
+    /// which policy artifact is live.
+    pub(crate) fn hash_model_file(model_path: &str) -> Result<String> {
+        let bytes = fs::read(model_path)
+            .with_context(|| format!("failed to read model file {}", model_path))?;
+        let mut hasher = Sha256::new();
+        hasher.update(&bytes);
+        Ok(format!("{:x}", hasher.finalize()))
+    }
+
+    static FORWARD_COUNT: AtomicU64 = AtomicU64::new(0);
+
+    /// Bump the forward-pass counter and record per-call latency, labeled by
+    /// backend, so both can be scraped the same way regardless of which policy
+    /// runtime served the request.
+    pub(crate) fn record_forward(backend: &'static str, start: Instant) {
+        FORWARD_COUNT.fetch_add(1, Ordering::Relaxed);
+        metrics::counter!("pit_model_forward_total", "backend" => backend).increment(1);
+        metrics::histogram!("pit_model_forward_latency_seconds", "backend" => backend)
+            .record(start.elapsed().as_secs_f64());
+    }
+
+    /// Total forward passes served by any backend since process start.
+    pub fn forward_count() -> u64 {
+        FORWARD_COUNT.load(Ordering::Relaxed)
+    }
+}
+
+#[cfg(feature = "std")]
+pub(crate) use host::{hash_model_file, load_meta, record_forward, MetaJson};
+#[cfg(feature = "std")]
+pub use host::{forward_count, Backend};
+
+/// Backend-agnostic pit/no-pit policy. `load` is excluded from the vtable
+/// (`Self: Sized`) so callers construct a concrete backend and then use it
+/// as `Box<dyn PitModel + Send + Sync>` everywhere else.
+pub trait PitModel {
+    fn load(model_path: &str, meta_path: &str) -> Result<Self>
+    where
+        Self: Sized;
+
+    fn in_dim(&self) -> usize;
+    fn feat_list(&self) -> &[String];
+    fn n_actions(&self) -> i64;
+    fn n_quant(&self) -> i64;
+    /// Content hash of the loaded model file, so deployments can log and
+    /// assert exactly which policy artifact is live.
+    fn model_version(&self) -> &str;
+
+    /// Returns (p2, p3): probability to box within 2 and 3 laps.
+    fn predict_probs(&self, x: &[f32]) -> Result<(f32, f32)>;
+    /// Risk-sensitive pit decision over the full quantile distribution; see
+    /// [`cvar_from_quantiles`].
+    fn predict_cvar(&self, x: &[f32], alpha: f64) -> Result<PitDecision>;
+}
+
+/// Load the policy named by `meta.json`'s `backend` field, dispatching to
+/// the matching `PitModel` implementation so users without libtorch can run
+/// an exported ONNX policy instead of requiring TorchScript. Needs a
+/// filesystem, so it's `std`-only; a `no_std` host builds its own
+/// `PitModel` and never calls this.
+#[cfg(feature = "std")]
+pub fn load_model(model_path: &str, meta_path: &str) -> Result<Box<dyn PitModel + Send + Sync>> {
+    let (meta, _in_dim) = load_meta(meta_path)?;
+    match meta.backend {
+        Backend::Torchscript => Ok(Box::new(TorchScriptModel::load(model_path, meta_path)?)),
+        Backend::Onnx => Ok(Box::new(OnnxModel::load(model_path, meta_path)?)),
+    }
+}
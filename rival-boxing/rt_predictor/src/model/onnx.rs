@@ -0,0 +1,156 @@
+use anyhow::{bail, Context, Result};
+use ort::session::Session;
+use ort::value::Value;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use super::{cvar_from_quantiles, hash_model_file, load_meta, record_forward, sigmoid, PitDecision, PitModel};
+
+/// ONNX Runtime-backed policy, for users without libtorch who still need to
+/// run an exported QR-DQN policy.
+///
+/// `ort::Session::run` takes `&mut self`, but `PitModel` callers only ever
+/// hold a shared `Arc<dyn PitModel + Send + Sync>`; the session is behind a
+/// `Mutex` so inference serializes rather than requiring `unsafe`.
+pub struct OnnxModel {
+    session: Mutex<Session>,
+    n_actions: i64,
+    n_quant: i64,
+    in_dim: usize,
+    feat_list: Vec<String>,
+    version: String,
+}
+
+impl OnnxModel {
+    /// Mean of action `a`'s quantiles from a flat `[1, A, Q]` row-major output.
+    fn action_quantiles<'a>(&self, flat: &'a [f32], action: usize) -> &'a [f32] {
+        let q = self.n_quant as usize;
+        let base = action * q;
+        &flat[base..base + q]
+    }
+}
+
+impl PitModel for OnnxModel {
+    fn load(model_path: &str, meta_path: &str) -> Result<Self> {
+        let (meta, in_dim) = load_meta(meta_path)?;
+
+        let session = Session::builder()
+            .context("failed to create ONNX Runtime session builder")?
+            .commit_from_file(model_path)
+            .with_context(|| format!("failed to load ONNX model {}", model_path))?;
+
+        // Probe output shape with a dummy forward — expect [B=1, A, Q]
+        let mut session = session;
+        let dummy = vec![0f32; in_dim];
+        let input = Value::from_array(([1usize, in_dim], dummy))
+            .context("failed to build ONNX probe input")?;
+        let outputs = session
+            .run(ort::inputs![input])
+            .context("ONNX warmup forward failed")?;
+        let (shape, _) = outputs[0]
+            .try_extract_raw_tensor::<f32>()
+            .context("unexpected ONNX output tensor kind")?;
+        if shape.len() != 3 || shape[0] != 1 {
+            bail!("unexpected model output size: {:?}", shape);
+        }
+        let n_actions = shape[1];
+        let n_quant = shape[2];
+        let version = hash_model_file(model_path)?;
+
+        Ok(Self {
+            session: Mutex::new(session),
+            n_actions,
+            n_quant,
+            in_dim,
+            feat_list: meta.feat_list,
+            version,
+        })
+    }
+
+    fn in_dim(&self) -> usize {
+        self.in_dim
+    }
+
+    fn feat_list(&self) -> &[String] {
+        &self.feat_list
+    }
+
+    fn n_actions(&self) -> i64 {
+        self.n_actions
+    }
+
+    fn n_quant(&self) -> i64 {
+        self.n_quant
+    }
+
+    fn model_version(&self) -> &str {
+        &self.version
+    }
+
+    fn predict_probs(&self, x: &[f32]) -> Result<(f32, f32)> {
+        if x.len() != self.in_dim {
+            bail!("feature length mismatch: got {}, expected {}", x.len(), self.in_dim);
+        }
+        let start = Instant::now();
+
+        let input = Value::from_array(([1usize, self.in_dim], x.to_vec()))
+            .context("failed to build ONNX input")?;
+        let mut session = self.session.lock().unwrap();
+        let outputs = session.run(ort::inputs![input]).context("ONNX forward failed")?;
+        let (_, data) = outputs[0]
+            .try_extract_raw_tensor::<f32>()
+            .context("unexpected ONNX output tensor kind")?;
+
+        let q = self.n_quant as f32;
+        let mean_no = self.action_quantiles(data, 0).iter().sum::<f32>() / q;
+        let mean_yes = self.action_quantiles(data, 1).iter().sum::<f32>() / q;
+        let gap = mean_yes - mean_no;
+
+        let p2 = sigmoid(gap);
+        let p3 = sigmoid(gap * 1.25);
+
+        drop(session);
+        record_forward("onnx", start);
+        Ok((p2, p3))
+    }
+
+    fn predict_cvar(&self, x: &[f32], alpha: f64) -> Result<PitDecision> {
+        if x.len() != self.in_dim {
+            bail!("feature length mismatch: got {}, expected {}", x.len(), self.in_dim);
+        }
+        let start = Instant::now();
+
+        let input = Value::from_array(([1usize, self.in_dim], x.to_vec()))
+            .context("failed to build ONNX input")?;
+        let mut session = self.session.lock().unwrap();
+        let outputs = session.run(ort::inputs![input]).context("ONNX forward failed")?;
+        let (_, data) = outputs[0]
+            .try_extract_raw_tensor::<f32>()
+            .context("unexpected ONNX output tensor kind")?;
+
+        let q = self.n_quant as f32;
+        let mut no_pit = self.action_quantiles(data, 0).to_vec();
+        let mut pit = self.action_quantiles(data, 1).to_vec();
+        if no_pit.iter().chain(pit.iter()).any(|v| !v.is_finite()) {
+            bail!("model emitted a non-finite quantile estimate");
+        }
+        no_pit.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        pit.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let cvar_no_pit = cvar_from_quantiles(&no_pit, alpha);
+        let cvar_pit = cvar_from_quantiles(&pit, alpha);
+        let mean_no_pit = no_pit.iter().sum::<f32>() / q;
+        let mean_pit = pit.iter().sum::<f32>() / q;
+
+        drop(session);
+        record_forward("onnx", start);
+        Ok(PitDecision {
+            alpha,
+            cvar_no_pit,
+            cvar_pit,
+            mean_no_pit,
+            mean_pit,
+            recommend_pit: cvar_pit > cvar_no_pit,
+        })
+    }
+}
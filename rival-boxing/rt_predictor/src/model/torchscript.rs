@@ -0,0 +1,152 @@
+use anyhow::{bail, Context, Result};
+use std::time::Instant;
+use tch::{kind::Kind, CModule, Device, IndexOp, Tensor};
+
+use super::{cvar_from_quantiles, hash_model_file, load_meta, record_forward, PitDecision, PitModel};
+
+/// TorchScript-backed policy, loaded via `tch`/libtorch.
+pub struct TorchScriptModel {
+    model: CModule,
+    device: Device,
+    n_actions: i64, // expected 2: [no_pit, pit]
+    n_quant: i64,   // number of quantiles per action
+    in_dim: usize,
+    feat_list: Vec<String>,
+    version: String,
+}
+
+impl PitModel for TorchScriptModel {
+    fn load(model_path: &str, meta_path: &str) -> Result<Self> {
+        let (meta, in_dim) = load_meta(meta_path)?;
+        let device = Device::Cpu;
+
+        let model = CModule::load_on_device(model_path, device)
+            .with_context(|| format!("failed to load TorchScript {}", model_path))?;
+
+        // Probe output shape with a dummy forward — expect [B=1, A, Q]
+        let dummy = Tensor::zeros([1, in_dim as i64], (Kind::Float, device));
+        let t = model.forward_ts(&[dummy])?;
+        let sz = t.size();
+        if sz.len() != 3 || sz[0] != 1 {
+            bail!("unexpected model output size: {:?}", sz);
+        }
+        let n_actions = sz[1];
+        let n_quant = sz[2];
+        let version = hash_model_file(model_path)?;
+
+        Ok(Self {
+            model,
+            device,
+            n_actions,
+            n_quant,
+            in_dim,
+            feat_list: meta.feat_list,
+            version,
+        })
+    }
+
+    fn in_dim(&self) -> usize {
+        self.in_dim
+    }
+
+    fn feat_list(&self) -> &[String] {
+        &self.feat_list
+    }
+
+    fn n_actions(&self) -> i64 {
+        self.n_actions
+    }
+
+    fn n_quant(&self) -> i64 {
+        self.n_quant
+    }
+
+    fn model_version(&self) -> &str {
+        &self.version
+    }
+
+    fn predict_probs(&self, x: &[f32]) -> Result<(f32, f32)> {
+        if x.len() != self.in_dim {
+            bail!("feature length mismatch: got {}, expected {}", x.len(), self.in_dim);
+        }
+        let start = Instant::now();
+
+        let input = Tensor::from_slice(x)
+            .reshape([1, self.in_dim as i64])
+            .to_device(self.device);
+
+        // Forward: [1, A, Q]
+        let t = self.model.forward_ts(&[input])?;
+
+        // Mean over quantiles (dim=2) -> [1, A]
+        let q_per_action = t.mean_dim(&[2i64][..], false, Kind::Float);
+        let sz = q_per_action.size();
+        if sz.len() != 2 || sz[0] != 1 || sz[1] < 2 {
+            bail!("unexpected q_per_action shape: {:?}", sz);
+        }
+
+        // gap = Q(pit) - Q(no_pit)
+        let q_no = q_per_action.i((0, 0));
+        let q_yes = q_per_action.i((0, 1));
+        let gap = (&q_yes - &q_no).to_kind(Kind::Float); // scalar tensor
+
+        // Tensor-native sigmoid; slightly steeper for 3-lap horizon
+        let p2_t = gap.sigmoid();
+        let p3_t = (gap * 1.25).sigmoid();
+
+        let p2 = p2_t.double_value(&[]) as f32;
+        let p3 = p3_t.double_value(&[]) as f32;
+
+        record_forward("torchscript", start);
+        Ok((p2, p3))
+    }
+
+    fn predict_cvar(&self, x: &[f32], alpha: f64) -> Result<PitDecision> {
+        if x.len() != self.in_dim {
+            bail!("feature length mismatch: got {}, expected {}", x.len(), self.in_dim);
+        }
+        let start = Instant::now();
+
+        let input = Tensor::from_slice(x)
+            .reshape([1, self.in_dim as i64])
+            .to_device(self.device);
+
+        // Forward: [1, A, Q]
+        let t = self.model.forward_ts(&[input])?;
+        let sz = t.size();
+        if sz.len() != 3 || sz[0] != 1 || sz[1] < 2 {
+            bail!("unexpected model output size: {:?}", sz);
+        }
+        let q = sz[2] as usize;
+
+        let extract_action = |action: i64| -> Vec<f32> {
+            let row = t.i((0, action));
+            (0..q as i64).map(|i| row.double_value(&[i]) as f32).collect()
+        };
+
+        // Quantile estimates must be sorted ascending to treat index order as
+        // the quantile function; QR-DQN heads aren't guaranteed monotonic.
+        let mut no_pit = extract_action(0);
+        let mut pit = extract_action(1);
+        if no_pit.iter().chain(pit.iter()).any(|v| !v.is_finite()) {
+            bail!("model emitted a non-finite quantile estimate");
+        }
+        no_pit.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+        pit.sort_by(|a, b| a.partial_cmp(b).unwrap_or(std::cmp::Ordering::Equal));
+
+        let cvar_no_pit = cvar_from_quantiles(&no_pit, alpha);
+        let cvar_pit = cvar_from_quantiles(&pit, alpha);
+        let mean_no_pit = no_pit.iter().sum::<f32>() / q as f32;
+        let mean_pit = pit.iter().sum::<f32>() / q as f32;
+
+        record_forward("torchscript", start);
+        Ok(PitDecision {
+            alpha,
+            cvar_no_pit,
+            cvar_pit,
+            mean_no_pit,
+            mean_pit,
+            recommend_pit: cvar_pit > cvar_no_pit,
+        })
+    }
+}
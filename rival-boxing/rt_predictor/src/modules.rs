@@ -0,0 +1,50 @@
+use anyhow::Result;
+
+use crate::{IngestFlat, Out};
+
+/// A pluggable stage in the `/ingest` pipeline. Modules run in registration
+/// order and can inspect/mutate the request, the ordered feature vector, or
+/// the response — letting add-ons like feature clamping, missing-feature
+/// rejection, or response post-processing hook in without editing the core
+/// handler. Any hook returning `Err` aborts the request with that error.
+///
+/// All hooks have a default no-op body so a module only needs to implement
+/// the stage(s) it cares about.
+pub trait InferenceModule {
+    /// Runs first, on the raw deserialized request.
+    fn on_request(&self, _req: &mut IngestFlat) -> Result<()> {
+        Ok(())
+    }
+
+    /// Runs after features are ordered into the model's expected layout
+    /// (see [`order_from_flat`]), before inference. `feat_list` is the
+    /// model's feature order, zipped positionally with `features`. Missing
+    /// keys arrive as `f32::NAN` so a module can tell "absent" apart from
+    /// "present and zero".
+    fn on_features(&self, _features: &mut Vec<f32>, _feat_list: &[String]) -> Result<()> {
+        Ok(())
+    }
+
+    /// Runs last, on the response about to be sent.
+    fn on_response(&self, _out: &mut Out) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Map missing features (`NaN`) to `0.0`, reproducing the historical default
+/// before this pipeline existed. Registered first so deployments without
+/// extra modules see no behavior change; a deployment that wants strict
+/// missing-feature rejection instead should register its own module ahead
+/// of this one and reject on `NAN` before it gets zero-filled.
+pub struct ZeroFillMissing;
+
+impl InferenceModule for ZeroFillMissing {
+    fn on_features(&self, features: &mut Vec<f32>, _feat_list: &[String]) -> Result<()> {
+        for v in features.iter_mut() {
+            if v.is_nan() {
+                *v = 0.0;
+            }
+        }
+        Ok(())
+    }
+}
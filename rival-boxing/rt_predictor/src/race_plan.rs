@@ -0,0 +1,310 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::collections::HashMap;
+
+use rt_predictor::types::{Compound, TelemetryPacket};
+
+/// Caller-supplied per-compound pace/degradation curve: the time lost, in
+/// seconds relative to a fresh-tyre baseline lap, from running `compound` at
+/// `tyre_age` laps since it went on. [`plan_strategy`] treats this as the
+/// full story of tyre wear; fuel effect, if any, is assumed baked in.
+pub trait PaceModel {
+    fn lap_penalty_s(&self, compound: Compound, tyre_age: i32) -> f32;
+}
+
+/// A [`PaceModel`] backed by a flat per-compound penalty curve handed over
+/// the wire — `penalties_s[compound.degradation_rank() - 1][tyre_age]` — so
+/// `/plan_strategy` can drive [`plan_strategy`] without baking any one
+/// team's tyre model into this crate. Ages past the end of a compound's
+/// curve hold at the last known penalty rather than erroring, since a
+/// driver running a stint longer than the caller bothered to supply data
+/// for should still get a (pessimistic) answer.
+pub struct TablePaceModel {
+    pub penalties_s: Vec<Vec<f32>>,
+}
+
+impl PaceModel for TablePaceModel {
+    fn lap_penalty_s(&self, compound: Compound, tyre_age: i32) -> f32 {
+        let Some(row) = self.penalties_s.get((compound.degradation_rank() - 1) as usize) else {
+            return 0.0;
+        };
+        let Some(&last) = row.last() else { return 0.0 };
+        let idx = (tyre_age.max(0) as usize).min(row.len() - 1);
+        row.get(idx).copied().unwrap_or(last)
+    }
+}
+
+/// The race state the optimizer plans forward from — the subset of
+/// `TelemetryPacket` the DP actually needs, with the optional fields
+/// resolved. Build one with [`RaceState::from_packet`].
+#[derive(Debug, Clone, Copy)]
+pub struct RaceState {
+    pub lap: i32,
+    pub compound: Compound,
+    pub tyre_laps: i32,
+    pub gap_front: f32,
+}
+
+impl RaceState {
+    /// Pulls `compound`, `tyre_laps`, `gap_front` out of a `TelemetryPacket`,
+    /// erroring if any is missing rather than guessing a default — the DP's
+    /// transition costs are meaningless without real tyre state.
+    pub fn from_packet(packet: &TelemetryPacket) -> Result<Self> {
+        Ok(Self {
+            lap: packet.lap,
+            compound: packet
+                .compound
+                .ok_or_else(|| anyhow!("race plan requires a known compound"))?,
+            tyre_laps: packet
+                .tyre_laps
+                .ok_or_else(|| anyhow!("race plan requires tyre_laps"))?,
+            gap_front: packet
+                .gap_front
+                .ok_or_else(|| anyhow!("race plan requires gap_front"))?,
+        })
+    }
+}
+
+/// Tunables for [`plan_strategy`].
+#[derive(Debug, Clone)]
+pub struct StrategyConfig {
+    /// Lap the race finishes on; laps remaining = `race_laps - state.lap`.
+    pub race_laps: i32,
+    /// Fixed pit-lane + out-lap cost of boxing, in seconds.
+    pub pit_loss_s: f32,
+    /// Compounds the optimizer is allowed to switch onto when boxing.
+    pub candidate_compounds: Vec<Compound>,
+}
+
+/// One projected pit stop in a [`StrategyPlan`].
+#[derive(Debug, Clone, Serialize)]
+pub struct PlannedStop {
+    pub lap: i32,
+    pub from_compound: Compound,
+    pub to_compound: Compound,
+    /// Cost of boxing on `lap` versus staying out one more lap on
+    /// `from_compound`, pit-lane loss included. The DP can still choose a
+    /// stop with a positive delta here if it saves more than that over the
+    /// laps that follow.
+    pub delta_s: f32,
+}
+
+/// A candidate race strategy: the stops it makes and the total time lost to
+/// tyre degradation and pit stops, projected to the end of the race.
+#[derive(Debug, Clone, Serialize)]
+pub struct StrategyPlan {
+    pub stops: Vec<PlannedStop>,
+    pub projected_loss_s: f32,
+    /// Projected change in the gap to the car ahead, this lap, if this
+    /// plan's first move is to box now; `None` for plans that stay out this
+    /// lap, since the undercut/overcut comparison only applies to boxing
+    /// immediately. Positive favors the undercut, negative the overcut.
+    pub undercut_margin_s: Option<f32>,
+}
+
+/// Projected gap to the car ahead after this lap if we box now vs. if we
+/// stay out: the undercut/overcut comparison. `box_now_cost_s` and
+/// `stay_out_cost_s` are this lap's predicted cost (pit loss included for
+/// the former). Positive means boxing now closes the gap (undercut
+/// favored); negative means staying out does (overcut favored).
+pub fn undercut_margin_s(gap_front: f32, box_now_cost_s: f32, stay_out_cost_s: f32) -> f32 {
+    gap_front - (box_now_cost_s - stay_out_cost_s)
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Decision {
+    StayOut,
+    Box(Compound),
+}
+
+/// Memo key: (laps simulated so far, compound, tyre age). `Compound` isn't
+/// `Hash`, so index it by its already-unique `degradation_rank`.
+type MemoKey = (i32, u8, i32);
+
+struct Solver<'a> {
+    model: &'a dyn PaceModel,
+    cfg: &'a StrategyConfig,
+    remaining: i32,
+    memo: HashMap<MemoKey, (f32, Decision)>,
+}
+
+impl<'a> Solver<'a> {
+    /// Minimum cumulative cost to reach the end of the race from `offset`
+    /// laps into the plan, on `compound` at `age` laps old, and the decision
+    /// (stay out or box onto some compound) that achieves it. Explores both
+    /// options at every lap, so by the time the root call returns, every
+    /// state reachable from it is memoized and a plan can be read back out
+    /// by following the stored decisions forward.
+    fn cost_to_go(&mut self, offset: i32, compound: Compound, age: i32) -> (f32, Decision) {
+        if offset >= self.remaining {
+            return (0.0, Decision::StayOut);
+        }
+        let key = (offset, compound.degradation_rank(), age);
+        if let Some(&cached) = self.memo.get(&key) {
+            return cached;
+        }
+
+        let stay_cost = self.model.lap_penalty_s(compound, age);
+        let (stay_rest, _) = self.cost_to_go(offset + 1, compound, age + 1);
+        let mut best = (stay_cost + stay_rest, Decision::StayOut);
+
+        for &to in &self.cfg.candidate_compounds {
+            let box_cost = self.cfg.pit_loss_s + self.model.lap_penalty_s(to, 0);
+            let (box_rest, _) = self.cost_to_go(offset + 1, to, 0);
+            let total = box_cost + box_rest;
+            if total < best.0 {
+                best = (total, Decision::Box(to));
+            }
+        }
+
+        self.memo.insert(key, best);
+        best
+    }
+
+    /// Walk the memoized decisions forward from `(offset, compound, age)`,
+    /// collecting the stops a plan starting there would make.
+    fn reconstruct(&self, state: &RaceState, mut offset: i32, mut compound: Compound, mut age: i32) -> Vec<PlannedStop> {
+        let mut stops = Vec::new();
+        while offset < self.remaining {
+            let key = (offset, compound.degradation_rank(), age);
+            let Some(&(_, decision)) = self.memo.get(&key) else { break };
+            match decision {
+                Decision::StayOut => {
+                    age += 1;
+                }
+                Decision::Box(to) => {
+                    let delta_s = (self.cfg.pit_loss_s + self.model.lap_penalty_s(to, 0))
+                        - self.model.lap_penalty_s(compound, age);
+                    stops.push(PlannedStop {
+                        lap: state.lap + offset,
+                        from_compound: compound,
+                        to_compound: to,
+                        delta_s,
+                    });
+                    compound = to;
+                    age = 0;
+                }
+            }
+            offset += 1;
+        }
+        stops
+    }
+}
+
+/// Forward DP over the remaining laps for the pit strategy that minimizes
+/// projected total race-time loss, returning the `top_k` best plans ranked
+/// ascending by `projected_loss_s`.
+///
+/// State is `(lap offset, current compound, tyre age)`; the transition cost
+/// at each lap is either `model.lap_penalty_s` for staying out (age grows
+/// by one) or `cfg.pit_loss_s + model.lap_penalty_s(to, 0)` for boxing (age
+/// resets). The root decision is solved for every candidate compound (and
+/// for staying out), each branch's continuation is optimal by construction,
+/// and the resulting plans are sorted so a strategist can compare the best
+/// few immediate choices rather than only ever seeing one answer.
+pub fn plan_strategy(
+    state: &RaceState,
+    model: &dyn PaceModel,
+    cfg: &StrategyConfig,
+    top_k: usize,
+) -> Result<Vec<StrategyPlan>> {
+    let remaining = cfg.race_laps - state.lap;
+    if remaining <= 0 {
+        return Err(anyhow!("no laps remain: race_laps {} <= current lap {}", cfg.race_laps, state.lap));
+    }
+    if cfg.candidate_compounds.is_empty() {
+        return Err(anyhow!("plan_strategy requires at least one candidate compound"));
+    }
+    if top_k == 0 {
+        return Err(anyhow!("top_k must be > 0"));
+    }
+
+    let mut solver = Solver { model, cfg, remaining, memo: HashMap::new() };
+    // Populates `solver.memo` for every state reachable from the root,
+    // including the root-level alternatives enumerated below.
+    solver.cost_to_go(0, state.compound, state.tyre_laps);
+
+    let stay_cost = model.lap_penalty_s(state.compound, state.tyre_laps);
+    let (stay_rest, _) = solver.cost_to_go(1, state.compound, state.tyre_laps + 1);
+
+    let mut candidates: Vec<(f32, Option<Compound>)> = vec![(stay_cost + stay_rest, None)];
+    for &to in &cfg.candidate_compounds {
+        let box_cost = cfg.pit_loss_s + model.lap_penalty_s(to, 0);
+        let (box_rest, _) = solver.cost_to_go(1, to, 0);
+        candidates.push((box_cost + box_rest, Some(to)));
+    }
+    candidates.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(std::cmp::Ordering::Equal));
+
+    let mut plans = Vec::with_capacity(top_k.min(candidates.len()));
+    for (projected_loss_s, first_move) in candidates.into_iter().take(top_k) {
+        let (stops, undercut_margin_s) = match first_move {
+            None => (solver.reconstruct(state, 1, state.compound, state.tyre_laps + 1), None),
+            Some(to) => {
+                let mut stops = vec![PlannedStop {
+                    lap: state.lap,
+                    from_compound: state.compound,
+                    to_compound: to,
+                    delta_s: (cfg.pit_loss_s + model.lap_penalty_s(to, 0)) - stay_cost,
+                }];
+                stops.extend(solver.reconstruct(state, 1, to, 0));
+                let margin = undercut_margin_s(
+                    state.gap_front,
+                    cfg.pit_loss_s + model.lap_penalty_s(to, 0),
+                    stay_cost,
+                );
+                (stops, Some(margin))
+            }
+        };
+        plans.push(StrategyPlan { stops, projected_loss_s, undercut_margin_s });
+    }
+
+    Ok(plans)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flat wear, no incentive to ever change compound: cheapest plan should
+    /// be "stay out" with zero stops.
+    fn flat_model() -> TablePaceModel {
+        TablePaceModel { penalties_s: vec![vec![1.0; 50]; 7] }
+    }
+
+    #[test]
+    fn flat_wear_curve_prefers_staying_out() {
+        let state = RaceState { lap: 10, compound: Compound::C3, tyre_laps: 5, gap_front: 2.0 };
+        let cfg = StrategyConfig { race_laps: 15, pit_loss_s: 20.0, candidate_compounds: vec![Compound::C1] };
+        let plans = plan_strategy(&state, &flat_model(), &cfg, 1).unwrap();
+        assert_eq!(plans[0].stops.len(), 0);
+    }
+
+    #[test]
+    fn steep_wear_on_current_compound_favors_an_early_stop() {
+        // Current compound (C5, rank 5) wears badly with age; the candidate
+        // (C1, rank 1) stays flat and cheap regardless of age.
+        let mut penalties_s = vec![vec![0.2; 50]; 7];
+        penalties_s[4] = (0..50).map(|age| age as f32 * 2.0).collect();
+        let model = TablePaceModel { penalties_s };
+
+        let state = RaceState { lap: 0, compound: Compound::C5, tyre_laps: 0, gap_front: 0.0 };
+        let cfg = StrategyConfig { race_laps: 20, pit_loss_s: 5.0, candidate_compounds: vec![Compound::C1] };
+        let plans = plan_strategy(&state, &model, &cfg, 1).unwrap();
+        assert!(!plans[0].stops.is_empty());
+    }
+
+    #[test]
+    fn rejects_a_race_already_over() {
+        let state = RaceState { lap: 20, compound: Compound::C1, tyre_laps: 0, gap_front: 0.0 };
+        let cfg = StrategyConfig { race_laps: 20, pit_loss_s: 20.0, candidate_compounds: vec![Compound::C2] };
+        assert!(plan_strategy(&state, &flat_model(), &cfg, 1).is_err());
+    }
+
+    #[test]
+    fn table_pace_model_holds_at_the_last_known_age() {
+        let model = TablePaceModel { penalties_s: vec![vec![1.0, 2.0, 3.0]; 7] };
+        assert_eq!(model.lap_penalty_s(Compound::C1, 0), 1.0);
+        assert_eq!(model.lap_penalty_s(Compound::C1, 2), 3.0);
+        assert_eq!(model.lap_penalty_s(Compound::C1, 100), 3.0);
+    }
+}
@@ -0,0 +1,190 @@
+use serde::Serialize;
+
+use rt_predictor::types::{Compound, PredictionOut, TelemetryPacket};
+
+/// Per-driver summary of one tyre stint, folded from the raw
+/// `TelemetryPacket`/`PredictionOut` stream at pit events and at race end —
+/// an auditable post-session report instead of only the per-lap
+/// probability firehose.
+#[derive(Debug, Clone, Serialize)]
+pub struct StintStatistic {
+    pub driver: String,
+    pub compound: Option<Compound>,
+    /// Laps run on this compound before the stint ended.
+    pub laps_run: i32,
+    /// Tyre age reached by the time the driver boxed (or by the last lap
+    /// seen, if the stint was still open when the stream ended).
+    pub tyre_age_at_box: i32,
+    /// This stint's nominal pit window, from the first packet's
+    /// `pit_window_lap`.
+    pub predicted_pit_lap: Option<i32>,
+    /// Lap the driver actually boxed, or `None` if the stint never ended
+    /// in an observed pit stop (e.g. stream ended mid-stint).
+    pub actual_pit_lap: Option<i32>,
+    /// `actual_pit_lap - predicted_pit_lap`, when both are known.
+    pub pit_lap_delta: Option<i32>,
+    /// Peak `prob_box_within2` reached at any point during the stint.
+    pub peak_prob_box_within2: f32,
+    /// First lap at which `prob_box_within2` crossed the aggregator's
+    /// configured threshold, if any.
+    pub first_lap_above_threshold: Option<i32>,
+}
+
+struct StintBuilder {
+    driver: String,
+    compound: Option<Compound>,
+    stint_start_lap: i32,
+    last_lap: i32,
+    last_tyre_laps: Option<i32>,
+    predicted_pit_lap: Option<i32>,
+    peak_prob_box_within2: f32,
+    first_lap_above_threshold: Option<i32>,
+}
+
+impl StintBuilder {
+    fn new(packet: &TelemetryPacket) -> Self {
+        Self {
+            driver: packet.driver.clone(),
+            compound: packet.compound,
+            stint_start_lap: packet.lap,
+            last_lap: packet.lap,
+            last_tyre_laps: packet.tyre_laps,
+            predicted_pit_lap: packet.pit_window_lap,
+            peak_prob_box_within2: 0.0,
+            first_lap_above_threshold: None,
+        }
+    }
+
+    fn finish(self, actual_pit_lap: Option<i32>) -> StintStatistic {
+        let pit_lap_delta = match (self.predicted_pit_lap, actual_pit_lap) {
+            (Some(predicted), Some(actual)) => Some(actual - predicted),
+            _ => None,
+        };
+        StintStatistic {
+            driver: self.driver,
+            compound: self.compound,
+            laps_run: self.last_lap - self.stint_start_lap + 1,
+            // Ground-truth tyre age from the last packet seen, not derived
+            // from lap arithmetic: the two agree only when the stint's
+            // first observed packet was already at tyre age 0 (e.g. an
+            // aggregator started mid-race otherwise under-counts).
+            tyre_age_at_box: self.last_tyre_laps.unwrap_or(self.last_lap - self.stint_start_lap + 1),
+            predicted_pit_lap: self.predicted_pit_lap,
+            actual_pit_lap,
+            pit_lap_delta,
+            peak_prob_box_within2: self.peak_prob_box_within2,
+            first_lap_above_threshold: self.first_lap_above_threshold,
+        }
+    }
+}
+
+/// Folds a `TelemetryPacket`/`PredictionOut` stream, one pair per lap, into
+/// a [`StintStatistic`] per completed stint. Feed laps in order via
+/// [`push`](Self::push); call [`finish`](Self::finish) once at race end.
+pub struct StintAggregator {
+    /// `prob_box_within2` level considered "the model flagged an imminent
+    /// stop", used for `first_lap_above_threshold`.
+    threshold: f32,
+    current: Option<StintBuilder>,
+    finished: Vec<StintStatistic>,
+}
+
+impl StintAggregator {
+    pub fn new(threshold: f32) -> Self {
+        Self { threshold, current: None, finished: Vec::new() }
+    }
+
+    /// Feed one lap's packet and the prediction computed for it. A change of
+    /// driver or compound, or `pitted_this_lap`, closes out the current
+    /// stint and starts a new one.
+    pub fn push(&mut self, packet: &TelemetryPacket, pred: &PredictionOut) {
+        let different_driver = self.current.as_ref().is_some_and(|s| s.driver != packet.driver);
+        let compound_changed = self.current.as_ref().is_some_and(|s| s.compound != packet.compound);
+        if different_driver || compound_changed {
+            if let Some(prev) = self.current.take() {
+                self.finished.push(prev.finish(None));
+            }
+        }
+
+        let builder = self.current.get_or_insert_with(|| StintBuilder::new(packet));
+        builder.last_lap = packet.lap;
+        builder.last_tyre_laps = packet.tyre_laps.or(builder.last_tyre_laps);
+        builder.peak_prob_box_within2 = builder.peak_prob_box_within2.max(pred.prob_box_within2);
+        if builder.first_lap_above_threshold.is_none() && pred.prob_box_within2 >= self.threshold {
+            builder.first_lap_above_threshold = Some(packet.lap);
+        }
+
+        if packet.pitted_this_lap.unwrap_or(false) {
+            if let Some(stint) = self.current.take() {
+                self.finished.push(stint.finish(Some(packet.lap)));
+            }
+        }
+    }
+
+    /// Close out any stint still in progress (race end) and return every
+    /// completed `StintStatistic`, in the order stints finished.
+    pub fn finish(mut self) -> Vec<StintStatistic> {
+        if let Some(stint) = self.current.take() {
+            self.finished.push(stint.finish(None));
+        }
+        self.finished
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn packet(driver: &str, lap: i32, tyre_laps: i32, pitted_this_lap: bool) -> TelemetryPacket {
+        TelemetryPacket {
+            driver: driver.to_string(),
+            lap,
+            compound: Some(Compound::C3),
+            tyre_laps: Some(tyre_laps),
+            gap_front: None,
+            track_status_code: None,
+            pit_window_lap: None,
+            pitted_this_lap: Some(pitted_this_lap),
+        }
+    }
+
+    fn pred(driver: &str, lap: i32, prob_box_within2: f32) -> PredictionOut {
+        PredictionOut {
+            driver: driver.to_string(),
+            lap,
+            prob_box_within2,
+            prob_box_within3: prob_box_within2,
+            ts_ms: 0,
+        }
+    }
+
+    #[test]
+    fn tyre_age_at_box_comes_from_packet_tyre_laps_not_lap_arithmetic() {
+        let mut agg = StintAggregator::new(0.5);
+        // Stint starts mid-race: first observed packet is already at tyre
+        // age 5, three laps before the car boxed.
+        agg.push(&packet("VER", 10, 5, false), &pred("VER", 10, 0.1));
+        agg.push(&packet("VER", 11, 6, false), &pred("VER", 11, 0.2));
+        agg.push(&packet("VER", 12, 7, true), &pred("VER", 12, 0.9));
+
+        let stints = agg.finish();
+        assert_eq!(stints.len(), 1);
+        // laps_run only knows about the 3 laps observed; tyre_age_at_box
+        // must reflect the real ground-truth age of 7, not laps_run's 3.
+        assert_eq!(stints[0].laps_run, 3);
+        assert_eq!(stints[0].tyre_age_at_box, 7);
+    }
+
+    #[test]
+    fn compound_change_closes_the_open_stint() {
+        let mut agg = StintAggregator::new(0.5);
+        agg.push(&packet("HAM", 1, 0, false), &pred("HAM", 1, 0.0));
+        let mut switched = packet("HAM", 2, 0, false);
+        switched.compound = Some(Compound::C5);
+        agg.push(&switched, &pred("HAM", 2, 0.0));
+
+        let stints = agg.finish();
+        assert_eq!(stints.len(), 2);
+        assert_eq!(stints[0].actual_pit_lap, None);
+    }
+}
@@ -0,0 +1,170 @@
+use anyhow::{anyhow, Result};
+use serde::Serialize;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use crate::model::PitModel;
+
+/// Tunables for [`search_pit_lap`].
+#[derive(Debug, Clone, Copy)]
+pub struct BeamConfig {
+    /// Nodes kept per depth; wider catches more of the cost landscape at
+    /// proportional cost.
+    pub beam_width: usize,
+    /// Number of laps ahead to search.
+    pub horizon: usize,
+    /// Fixed time cost of boxing (pit-lane loss + out-lap), in seconds.
+    pub pit_loss_s: f32,
+    /// CVaR confidence level passed to `model.predict_cvar` at every node.
+    /// `1.0` reduces the cost estimate to the plain quantile mean; lower
+    /// values (e.g. `0.2`) bias the search away from paths whose worst-case
+    /// tail is expensive even if their mean looks fine.
+    pub alpha: f64,
+}
+
+/// One node of the beam: the feature vector at this lap offset, the
+/// cumulative predicted time cost to reach it, and whether the car has
+/// already pitted along this path.
+#[derive(Debug, Clone)]
+struct BeamNode {
+    features: Vec<f32>,
+    lap_offset: usize,
+    cumulative_cost_s: f32,
+    pitted: bool,
+    pit_lap: Option<usize>,
+}
+
+impl PartialEq for BeamNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cumulative_cost_s == other.cumulative_cost_s
+    }
+}
+impl Eq for BeamNode {}
+
+// Reversed so a plain `BinaryHeap` (a max-heap) pops the lowest-cost node
+// first, i.e. behaves as the min-heap the beam-search pattern wants.
+impl PartialOrd for BeamNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+impl Ord for BeamNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other
+            .cumulative_cost_s
+            .partial_cmp(&self.cumulative_cost_s)
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
+/// Result of a beam search over the pit-lap decision: which lap to box on,
+/// and the projected time saved versus pitting this lap.
+#[derive(Debug, Clone, Serialize)]
+pub struct PitLapPlan {
+    /// Lap offset (0 = this lap) the search recommends boxing on.
+    pub pit_lap: usize,
+    /// Positive means the plan is projected faster than pitting now.
+    pub projected_delta_s: f32,
+}
+
+/// Bounded beam search over the next `cfg.horizon` laps for the pit lap that
+/// minimizes projected cumulative time cost.
+///
+/// `roll_forward` advances a feature vector by one lap (tyre age +1, fuel
+/// burn, etc.) and is supplied by the caller since feature layout is
+/// model-specific. At each node, "stay out" adds the model's no-pit CVaR at
+/// `cfg.alpha` as the predicted green-lap cost, and — if not yet pitted —
+/// "pit this lap" adds `pit_loss_s` plus the pit-action CVaR. Only the
+/// `beam_width` lowest-cumulative-cost nodes survive each depth.
+pub fn search_pit_lap(
+    model: &dyn PitModel,
+    features: &[f32],
+    roll_forward: impl Fn(&[f32]) -> Vec<f32>,
+    cfg: BeamConfig,
+) -> Result<PitLapPlan> {
+    if cfg.horizon == 0 || cfg.beam_width == 0 {
+        return Err(anyhow!("beam search requires horizon > 0 and beam_width > 0"));
+    }
+
+    let mut beam: BinaryHeap<BeamNode> = BinaryHeap::new();
+    beam.push(BeamNode {
+        features: features.to_vec(),
+        lap_offset: 0,
+        cumulative_cost_s: 0.0,
+        pitted: false,
+        pit_lap: None,
+    });
+
+    for _ in 0..cfg.horizon {
+        let mut expanded: BinaryHeap<BeamNode> = BinaryHeap::new();
+        while let Some(node) = beam.pop() {
+            let decision = model.predict_cvar(&node.features, cfg.alpha)?;
+            let rolled = roll_forward(&node.features);
+
+            // Cost is the CVaR tail estimate, not the plain mean, so a path
+            // whose worst case is expensive gets penalized even when its
+            // mean looks fine — what `cfg.alpha` is for.
+            expanded.push(BeamNode {
+                features: rolled.clone(),
+                lap_offset: node.lap_offset + 1,
+                cumulative_cost_s: node.cumulative_cost_s + decision.cvar_no_pit,
+                pitted: node.pitted,
+                pit_lap: node.pit_lap,
+            });
+
+            if !node.pitted {
+                expanded.push(BeamNode {
+                    features: rolled,
+                    lap_offset: node.lap_offset + 1,
+                    cumulative_cost_s: node.cumulative_cost_s + cfg.pit_loss_s + decision.cvar_pit,
+                    pitted: true,
+                    pit_lap: Some(node.lap_offset),
+                });
+            }
+        }
+
+        // `pop()` yields the lowest-cost node first (see the `Ord` impl
+        // above), so draining `beam_width` pops is exactly "keep the
+        // beam_width lowest-cumulative-cost nodes".
+        let mut kept = BinaryHeap::with_capacity(cfg.beam_width);
+        for _ in 0..cfg.beam_width {
+            match expanded.pop() {
+                Some(node) => kept.push(node),
+                None => break,
+            }
+        }
+        beam = kept;
+    }
+
+    let best = beam
+        .pop()
+        .ok_or_else(|| anyhow!("beam search produced no terminal nodes"))?;
+
+    let pit_now_cost = project_pit_now_cost(model, features, &roll_forward, cfg)?;
+
+    Ok(PitLapPlan {
+        pit_lap: best.pit_lap.unwrap_or(cfg.horizon),
+        projected_delta_s: pit_now_cost - best.cumulative_cost_s,
+    })
+}
+
+/// Cost of the baseline "pit this lap" plan, projected over the same
+/// horizon, so `projected_delta_s` compares like-for-like cumulative costs.
+fn project_pit_now_cost(
+    model: &dyn PitModel,
+    features: &[f32],
+    roll_forward: &impl Fn(&[f32]) -> Vec<f32>,
+    cfg: BeamConfig,
+) -> Result<f32> {
+    let decision = model.predict_cvar(features, cfg.alpha)?;
+    let mut cost = cfg.pit_loss_s + decision.cvar_pit;
+    let mut current = roll_forward(features);
+
+    for _ in 1..cfg.horizon {
+        let decision = model.predict_cvar(&current, cfg.alpha)?;
+        cost += decision.cvar_no_pit;
+        current = roll_forward(&current);
+    }
+
+    Ok(cost)
+}
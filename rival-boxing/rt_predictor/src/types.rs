@@ -1,23 +1,107 @@
+#[cfg(not(feature = "std"))]
+extern crate alloc;
+#[cfg(not(feature = "std"))]
+use alloc::string::String;
+
+#[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
+#[cfg(feature = "serde")]
+use serde_repr::{Deserialize_repr, Serialize_repr};
+
+/// Tyre compound, validated against the finite set F1 timing feeds actually
+/// emit rather than accepted as free text. Wire format keeps the feed's own
+/// short codes (`"C1"`..`"C5"`, `"I"`, `"W"`); an unrecognized code is a
+/// deserialization error instead of a silently-stored junk string.
+///
+/// Plain enum with no wire format at all when the `serde` feature is off —
+/// a `no_std` host (see [`crate::clock`]) builds its own `TelemetryPacket`
+/// by hand instead of deserializing one, so the `serde` derives and their
+/// wire-code attributes would otherwise be dead weight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub enum Compound {
+    #[cfg_attr(feature = "serde", serde(rename = "C1"))]
+    C1,
+    #[cfg_attr(feature = "serde", serde(rename = "C2"))]
+    C2,
+    #[cfg_attr(feature = "serde", serde(rename = "C3"))]
+    C3,
+    #[cfg_attr(feature = "serde", serde(rename = "C4"))]
+    C4,
+    #[cfg_attr(feature = "serde", serde(rename = "C5"))]
+    C5,
+    #[cfg_attr(feature = "serde", serde(rename = "I"))]
+    Intermediate,
+    #[cfg_attr(feature = "serde", serde(rename = "W"))]
+    Wet,
+}
 
-#[derive(Debug, Deserialize)]
+impl Compound {
+    /// `true` for the dry slick compounds (C1-C5), `false` for wet-weather
+    /// tyres, so feature-rebuilding code stops string-matching on the wire
+    /// code to tell them apart.
+    pub fn is_slick(&self) -> bool {
+        !matches!(self, Compound::Intermediate | Compound::Wet)
+    }
+
+    /// Relative wear rate, lowest to highest: C1 is the hardest slick and
+    /// wears slowest, C5 the softest and wears fastest; wet-weather
+    /// compounds rank above all slicks since they're never chosen for pace.
+    pub fn degradation_rank(&self) -> u8 {
+        match self {
+            Compound::C1 => 1,
+            Compound::C2 => 2,
+            Compound::C3 => 3,
+            Compound::C4 => 4,
+            Compound::C5 => 5,
+            Compound::Intermediate => 6,
+            Compound::Wet => 7,
+        }
+    }
+}
+
+/// Track status as used in F1 timing feeds, validated against the known
+/// numeric codes rather than accepted as a bare `i32`. Wire format stays
+/// the feed's own integer codes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize_repr, Deserialize_repr))]
+#[repr(i32)]
+pub enum TrackStatus {
+    Green = 1,
+    Yellow = 2,
+    SafetyCar = 4,
+    Red = 5,
+    VirtualSafetyCar = 6,
+}
+
+/// Core per-lap state the predictor and the strategy math run on. Deriving
+/// `Deserialize` only under the `serde` feature keeps this usable as a
+/// plain struct on a `no_std` host that assembles it itself (e.g. from a
+/// CAN bus decoder) instead of parsing JSON off a socket.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(Deserialize))]
 pub struct TelemetryPacket {
     pub driver: String,
     pub lap: i32,
     // fields we use to rebuild features (extend as you add more features):
-    pub compound: Option<String>,        // e.g., "C1","C2","C3","C4","C5","I","W"
+    pub compound: Option<Compound>,
     pub tyre_laps: Option<i32>,          // age in laps
     pub gap_front: Option<f32>,          // to car ahead (s)
-    pub track_status_code: Option<i32>,  // 1=green; others non-green
+    pub track_status_code: Option<TrackStatus>,
     pub pit_window_lap: Option<i32>,     // nominal planned pit window (lap)
     pub pitted_this_lap: Option<bool>,   // optional ground truth marker (for debug)
 }
 
-#[derive(Debug, Serialize, Clone)]
+/// `ts_ms` is always a plain `i64` here, never read from the system clock by
+/// this type itself — callers stamp it from whatever [`crate::clock::Clock`]
+/// they have registered, which is what lets this struct exist unchanged on
+/// hosts with no OS clock.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct PredictionOut {
     pub driver: String,
     pub lap: i32,
     pub prob_box_within2: f32,
     pub prob_box_within3: f32,
     pub ts_ms: i64,
-}
\ No newline at end of file
+}
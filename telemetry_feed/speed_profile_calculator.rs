@@ -9,6 +9,109 @@ pub struct SpeedSample {
     pub v_mps: f64,
 }
 
+/// Running mean of `v_mps` for samples folded into the currently-open
+/// distance bin, keyed by the bin's lower edge.
+struct ActiveBin {
+    bin_lo: f64,
+    count: u32,
+    sum_v: f64,
+}
+
+/// Default width, in meters, within which consecutive samples are assumed
+/// to be part of the same covered stretch of telemetry.
+const DEFAULT_MAX_GAP_M: f64 = 25.0;
+
+/// Minimum speed (m/s) a profile segment must have on both ends for
+/// [`SpeedProfileCalculator::time_to_travel`] to integrate across it;
+/// below this, `1/v` blows up and the segment is treated as impassable
+/// rather than returning a nonsensical ETA.
+const MIN_INTEGRABLE_V_MPS: f64 = 0.05;
+
+/// Tracks which stretches of lap distance are actually backed by telemetry
+/// samples, as a sorted set of non-overlapping `[lo, hi]` intervals.
+/// Consecutive samples closer together than `max_gap_m` are coalesced into
+/// the same interval; anything further apart leaves a hole that callers can
+/// detect instead of silently interpolating across it.
+struct RangeTracker {
+    max_gap_m: f64,
+    intervals: Vec<(f64, f64)>,
+}
+
+impl RangeTracker {
+    fn new(max_gap_m: f64) -> Self {
+        Self { max_gap_m, intervals: Vec::new() }
+    }
+
+    fn add_sample(&mut self, x: f64) {
+        self.intervals.push((x, x));
+        self.intervals.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+
+        let mut merged: Vec<(f64, f64)> = Vec::with_capacity(self.intervals.len());
+        for &(lo, hi) in &self.intervals {
+            match merged.last_mut() {
+                Some(last) if lo - last.1 <= self.max_gap_m => {
+                    last.1 = last.1.max(hi);
+                }
+                _ => merged.push((lo, hi)),
+            }
+        }
+        self.intervals = merged;
+    }
+
+    /// Sub-intervals of `[start, end]` that are covered by telemetry.
+    fn coverage(&self, start: f64, end: f64) -> Vec<(f64, f64)> {
+        self.intervals
+            .iter()
+            .filter_map(|&(lo, hi)| {
+                let clo = lo.max(start);
+                let chi = hi.min(end);
+                if clo < chi { Some((clo, chi)) } else { None }
+            })
+            .collect()
+    }
+
+    /// Sub-intervals of `[start, end]` that are NOT covered by telemetry —
+    /// where interpolation would be extrapolating across a hole.
+    fn gaps(&self, start: f64, end: f64) -> Vec<(f64, f64)> {
+        let mut gaps = Vec::new();
+        let mut cursor = start;
+        for (lo, hi) in self.coverage(start, end) {
+            if lo > cursor {
+                gaps.push((cursor, lo));
+            }
+            cursor = cursor.max(hi);
+        }
+        if cursor < end {
+            gaps.push((cursor, end));
+        }
+        gaps
+    }
+
+    fn is_fully_covered(&self, start: f64, end: f64) -> bool {
+        start >= end || self.gaps(start, end).is_empty()
+    }
+
+    fn reset(&mut self) {
+        self.intervals.clear();
+    }
+}
+
+/// A speed profile together with any gaps inside the requested range that
+/// aren't actually backed by telemetry — callers use this to tell a
+/// trustworthy integral apart from one that's extrapolating across a hole.
+#[derive(Debug, Clone, Serialize)]
+pub struct ProfileReport {
+    pub samples: Vec<SpeedSample>,
+    /// Uncovered `[lo, hi]` sub-intervals of the requested range, if any.
+    pub gaps: Vec<(f64, f64)>,
+}
+
+impl ProfileReport {
+    pub fn is_fully_covered(&self) -> bool {
+        self.gaps.is_empty()
+    }
+}
+
 /// Maintains a sliding window of recent telemetry and generates
 /// speed profiles for integration-based time estimation.
 pub struct SpeedProfileCalculator {
@@ -18,6 +121,12 @@ pub struct SpeedProfileCalculator {
     lookahead_m: f64,
     /// Sliding window of recent telemetry samples
     window: VecDeque<SpeedSample>,
+    /// Width of distance bins for pre-averaging, if enabled
+    bin_m: Option<f64>,
+    /// Bin currently being accumulated (not yet pushed to `window`)
+    active_bin: Option<ActiveBin>,
+    /// Tracks which stretches of lap distance are backed by raw samples
+    coverage: RangeTracker,
 }
 
 impl SpeedProfileCalculator {
@@ -31,9 +140,40 @@ impl SpeedProfileCalculator {
             window_size,
             lookahead_m,
             window: VecDeque::with_capacity(window_size),
+            bin_m: None,
+            active_bin: None,
+            coverage: RangeTracker::new(DEFAULT_MAX_GAP_M),
         }
     }
 
+    /// Create a calculator that pre-averages raw samples into fixed-width
+    /// distance cells before they reach the sliding window.
+    ///
+    /// Noisy high-rate telemetry produces a jittery profile that the
+    /// integrator then faithfully follows; binning in distance first yields a
+    /// denser-but-smoother series at the same window size.
+    ///
+    /// # Arguments
+    /// * `window_size` - Number of averaged samples to keep in the sliding window
+    /// * `lookahead_m` - Distance ahead (meters) to include in the profile for integration
+    /// * `bin_m` - Width of each distance cell in meters
+    pub fn new_with_binning(window_size: usize, lookahead_m: f64, bin_m: f64) -> Self {
+        Self {
+            window_size,
+            lookahead_m,
+            window: VecDeque::with_capacity(window_size),
+            bin_m: Some(bin_m),
+            active_bin: None,
+            coverage: RangeTracker::new(DEFAULT_MAX_GAP_M),
+        }
+    }
+
+    /// Override the max-gap width (meters) used to decide whether two
+    /// consecutive samples belong to the same covered stretch.
+    pub fn set_max_gap_m(&mut self, max_gap_m: f64) {
+        self.coverage.max_gap_m = max_gap_m;
+    }
+
     /// Add a new telemetry sample to the sliding window.
     ///
     /// # Arguments
@@ -41,12 +181,37 @@ impl SpeedProfileCalculator {
     /// * `speed_kph` - Current speed in km/h
     pub fn add_sample(&mut self, lap_distance_m: f64, speed_kph: f64) {
         let speed_mps = speed_kph / 3.6; // Convert to m/s
-        
-        let sample = SpeedSample {
-            x_m: lap_distance_m,
-            v_mps: speed_mps,
-        };
+        self.coverage.add_sample(lap_distance_m);
 
+        match self.bin_m {
+            Some(bin_m) => self.add_binned_sample(lap_distance_m, speed_mps, bin_m),
+            None => self.push_window(SpeedSample { x_m: lap_distance_m, v_mps: speed_mps }),
+        }
+    }
+
+    /// Fold `(x, v)` into the active distance bin, finalizing and pushing the
+    /// previous bin's running average once the car crosses into a new cell.
+    fn add_binned_sample(&mut self, x: f64, v: f64, bin_m: f64) {
+        let bin_lo = (x / bin_m).floor() * bin_m;
+
+        match &mut self.active_bin {
+            Some(active) if active.bin_lo == bin_lo => {
+                active.count += 1;
+                active.sum_v += v;
+            }
+            _ => {
+                if let Some(finished) = self.active_bin.take() {
+                    self.push_window(SpeedSample {
+                        x_m: finished.bin_lo + bin_m / 2.0,
+                        v_mps: finished.sum_v / finished.count as f64,
+                    });
+                }
+                self.active_bin = Some(ActiveBin { bin_lo, count: 1, sum_v: v });
+            }
+        }
+    }
+
+    fn push_window(&mut self, sample: SpeedSample) {
         if self.window.len() >= self.window_size {
             self.window.pop_front();
         }
@@ -56,15 +221,17 @@ impl SpeedProfileCalculator {
     /// Generate a speed profile from current position toward target.
     ///
     /// Returns samples in the range [current_distance_m, target_distance_m]
-    /// or slightly beyond if we have historical data that covers that range.
+    /// or slightly beyond if we have historical data that covers that range,
+    /// together with any gaps inside [current_distance_m, target_distance_m]
+    /// that aren't actually backed by telemetry.
     ///
     /// # Arguments
     /// * `current_distance_m` - Current lap distance in meters
     /// * `target_distance_m` - Target call point distance in meters
     ///
     /// # Returns
-    /// `Some(Vec<SpeedSample>)` if sufficient data exists, `None` otherwise
-    pub fn get_profile(&self, current_distance_m: f64, target_distance_m: f64) -> Option<Vec<SpeedSample>> {
+    /// `Some(ProfileReport)` if sufficient data exists, `None` otherwise
+    pub fn get_profile(&self, current_distance_m: f64, target_distance_m: f64) -> Option<ProfileReport> {
         if self.window.len() < 2 {
             return None;
         }
@@ -75,21 +242,33 @@ impl SpeedProfileCalculator {
         let min_x = current_distance_m - BUFFER_M;
         let max_x = target_distance_m + BUFFER_M;
 
-        let mut profile: Vec<SpeedSample> = self.window
+        let mut samples: Vec<SpeedSample> = self.window
             .iter()
             .filter(|sample| sample.x_m >= min_x && sample.x_m <= max_x)
             .cloned()
             .collect();
 
         // Need at least 2 points for meaningful integration
-        if profile.len() < 2 {
+        if samples.len() < 2 {
             return None;
         }
 
         // Sort by distance to ensure monotonic increasing x
-        profile.sort_by(|a, b| a.x_m.partial_cmp(&b.x_m).unwrap());
+        samples.sort_by(|a, b| a.x_m.partial_cmp(&b.x_m).unwrap());
+
+        let gaps = self.coverage.gaps(current_distance_m, target_distance_m);
+        Some(ProfileReport { samples, gaps })
+    }
 
-        Some(profile)
+    /// Covered `[lo, hi]` sub-intervals of `[start, end]`, per raw telemetry
+    /// (independent of any distance-binning applied to the profile itself).
+    pub fn coverage(&self, start: f64, end: f64) -> Vec<(f64, f64)> {
+        self.coverage.coverage(start, end)
+    }
+
+    /// Whether `[start, end]` is entirely backed by telemetry samples.
+    pub fn is_fully_covered(&self, start: f64, end: f64) -> bool {
+        self.coverage.is_fully_covered(start, end)
     }
 
     /// Generate a speed profile looking ahead from current position.
@@ -100,8 +279,9 @@ impl SpeedProfileCalculator {
     /// * `current_distance_m` - Current lap distance in meters
     ///
     /// # Returns
-    /// List of speed samples ahead of current position, or None if insufficient
-    pub fn get_lookahead_profile(&self, current_distance_m: f64) -> Option<Vec<SpeedSample>> {
+    /// Speed samples ahead of current position plus any coverage gaps, or
+    /// None if insufficient
+    pub fn get_lookahead_profile(&self, current_distance_m: f64) -> Option<ProfileReport> {
         if self.window.len() < 2 {
             return None;
         }
@@ -110,15 +290,106 @@ impl SpeedProfileCalculator {
         self.get_profile(current_distance_m, target_distance_m)
     }
 
+    /// Speed at distance `x`, linearly interpolated between the two
+    /// straddling samples, or held constant at the nearest sample's speed if
+    /// `x` falls outside the window's covered range (e.g. a lookahead
+    /// target ahead of the last telemetry tick). `None` only if the window
+    /// is empty.
+    fn extrapolated_v_at(&self, x: f64) -> Option<f64> {
+        let mut sorted: Vec<&SpeedSample> = self.window.iter().collect();
+        sorted.sort_by(|a, b| a.x_m.partial_cmp(&b.x_m).unwrap());
+
+        let first = *sorted.first()?;
+        let last = *sorted.last()?;
+        if x <= first.x_m {
+            return Some(first.v_mps);
+        }
+        if x >= last.x_m {
+            return Some(last.v_mps);
+        }
+
+        for w in sorted.windows(2) {
+            let (a, b) = (w[0], w[1]);
+            if x >= a.x_m && x <= b.x_m {
+                if (b.x_m - a.x_m).abs() < f64::EPSILON {
+                    return Some(a.v_mps);
+                }
+                let t = (x - a.x_m) / (b.x_m - a.x_m);
+                return Some(a.v_mps + t * (b.v_mps - a.v_mps));
+            }
+        }
+        Some(last.v_mps)
+    }
+
+    /// Integrate `dt = dx / v(x)` from `from_x` to `to_x` over the sliding
+    /// window's speed profile, using the trapezoidal rule on `1/v` between
+    /// consecutive samples. Endpoints that fall inside the window are
+    /// linearly interpolated; endpoints beyond it extrapolate at the
+    /// nearest sample's speed.
+    ///
+    /// Returns `None` if the window has fewer than two samples, or if any
+    /// segment's speed dips below [`MIN_INTEGRABLE_V_MPS`] — integrating
+    /// across a near-stationary segment would blow up, and there's no
+    /// caller-supplied floor speed here to fall back to.
+    pub fn time_to_travel(&self, from_x: f64, to_x: f64) -> Option<f64> {
+        if to_x <= from_x {
+            return Some(0.0);
+        }
+        if self.window.len() < 2 {
+            return None;
+        }
+
+        let v_from = self.extrapolated_v_at(from_x)?;
+        let v_to = self.extrapolated_v_at(to_x)?;
+
+        let mut pts: Vec<(f64, f64)> = self.window
+            .iter()
+            .filter(|s| s.x_m > from_x && s.x_m < to_x)
+            .map(|s| (s.x_m, s.v_mps))
+            .collect();
+        pts.insert(0, (from_x, v_from));
+        pts.push((to_x, v_to));
+        pts.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        pts.dedup_by(|a, b| a.0 == b.0);
+
+        let mut dt = 0.0;
+        for w in pts.windows(2) {
+            let (x0, v0) = w[0];
+            let (x1, v1) = w[1];
+            if v0 < MIN_INTEGRABLE_V_MPS || v1 < MIN_INTEGRABLE_V_MPS {
+                return None;
+            }
+            dt += (x1 - x0) * 0.5 * (1.0 / v0 + 1.0 / v1);
+        }
+        Some(dt)
+    }
+
+    /// Predicted time-of-arrival at `target_x`, integrating forward from the
+    /// most recent sample's position via [`time_to_travel`].
+    pub fn predicted_arrival(&self, target_x: f64) -> Option<f64> {
+        let current_x = self.window.back()?.x_m;
+        self.time_to_travel(current_x, target_x)
+    }
+
     /// Clear the sliding window.
     pub fn reset(&mut self) {
         self.window.clear();
+        self.active_bin = None;
+        self.coverage.reset();
     }
 
     /// Get the current window size
     pub fn window_len(&self) -> usize {
         self.window.len()
     }
+
+    /// Number of samples in the sliding window that fall within `[start, end]`
+    /// (order-independent), for callers that need "enough in-range samples"
+    /// rather than just "enough samples somewhere in the window".
+    pub fn samples_in_range(&self, start: f64, end: f64) -> usize {
+        let (lo, hi) = if start <= end { (start, end) } else { (end, start) };
+        self.window.iter().filter(|s| s.x_m >= lo && s.x_m <= hi).count()
+    }
 }
 
 #[cfg(test)]
@@ -141,8 +412,8 @@ mod tests {
         let profile = calc.get_lookahead_profile(current_distance);
 
         assert!(profile.is_some(), "Profile should be generated");
-        let profile = profile.unwrap();
-        
+        let profile = profile.unwrap().samples;
+
         println!("✓ Generated profile with {} samples", profile.len());
         println!("  First sample: x={:.1}m, v={:.2}m/s", profile[0].x_m, profile[0].v_mps);
         println!("  Last sample:  x={:.1}m, v={:.2}m/s", profile.last().unwrap().x_m, profile.last().unwrap().v_mps);
@@ -174,7 +445,7 @@ mod tests {
         let profile = calc.get_profile(2200.0, 2400.0);
 
         assert!(profile.is_some(), "Target range profile should be generated");
-        let profile = profile.unwrap();
+        let profile = profile.unwrap().samples;
 
         println!("✓ Target range profile: {} samples from {:.1}m to {:.1}m", 
             profile.len(), profile[0].x_m, profile.last().unwrap().x_m);
@@ -215,7 +486,7 @@ mod tests {
         let profile = calc.get_lookahead_profile(3180.0);
         
         assert!(profile.is_some(), "Profile should be generated");
-        let profile = profile.unwrap();
+        let profile = profile.unwrap().samples;
 
         // Test JSON serialization
         let json_str = serde_json::to_string_pretty(&profile).expect("Should serialize to JSON");
@@ -247,6 +518,85 @@ mod tests {
         assert_eq!(calc.window_len(), 5, "Window should be limited to window_size");
     }
 
+    #[test]
+    fn test_binned_averaging_folds_samples_within_a_cell() {
+        let mut calc = SpeedProfileCalculator::new_with_binning(20, 200.0, 10.0);
+
+        // Several noisy ticks within the same 10m cell [1000, 1010)
+        calc.add_sample(1000.0, 90.0);
+        calc.add_sample(1002.0, 100.0);
+        calc.add_sample(1008.0, 110.0);
+        // Crossing into the next cell finalizes the previous bin
+        calc.add_sample(1011.0, 120.0);
+
+        assert_eq!(calc.window_len(), 1, "first bin should only finalize once we leave it");
+
+        let profile = calc.get_profile(900.0, 1100.0).unwrap().samples;
+        let expected_mps = (90.0 + 100.0 + 110.0) / 3.0 / 3.6;
+        assert!((profile[0].v_mps - expected_mps).abs() < 1e-9, "bin should average folded samples");
+        assert!((profile[0].x_m - 1005.0).abs() < 1e-9, "bin should be reported at its midpoint");
+    }
+
+    #[test]
+    fn test_range_tracker_reports_gaps_in_requested_range() {
+        let mut calc = SpeedProfileCalculator::new(50, 500.0);
+
+        // Dense telemetry from 1000m to 1100m
+        for distance in (1000..1100).step_by(5) {
+            calc.add_sample(distance as f64, 100.0);
+        }
+        // A hole, then dense telemetry resumes from 1300m to 1400m
+        for distance in (1300..1400).step_by(5) {
+            calc.add_sample(distance as f64, 100.0);
+        }
+
+        assert!(!calc.is_fully_covered(1000.0, 1400.0), "gap between 1100m and 1300m should be detected");
+        assert!(calc.is_fully_covered(1000.0, 1090.0), "densely sampled sub-range should be fully covered");
+
+        let report = calc.get_profile(1000.0, 1400.0).unwrap();
+        assert!(!report.gaps.is_empty(), "report should surface the coverage gap");
+        assert!(!report.is_fully_covered());
+
+        let (gap_lo, gap_hi) = report.gaps[0];
+        assert!(gap_lo >= 1095.0 && gap_hi <= 1305.0, "gap should fall between the two covered stretches, got {:?}", report.gaps);
+    }
+
+    #[test]
+    fn test_time_to_travel_constant_speed() {
+        let mut calc = SpeedProfileCalculator::new(60, 200.0);
+
+        // Constant 36 kph = 10 m/s over a 1000m->1500m straight
+        for distance in (1000..1500).step_by(10) {
+            calc.add_sample(distance as f64, 36.0);
+        }
+
+        let dt = calc.time_to_travel(1000.0, 1490.0).expect("should integrate over constant speed");
+        assert!((dt - 49.0).abs() < 0.1, "490m at 10 m/s should take ~49s, got {}", dt);
+
+        // predicted_arrival looks ahead of the most recent sample (1490m),
+        // extrapolating at its speed since there's no telemetry beyond it yet.
+        let eta = calc.predicted_arrival(1590.0).expect("should predict arrival at target");
+        assert!((eta - 10.0).abs() < 0.1, "100m further at 10 m/s should take ~10s, got {}", eta);
+    }
+
+    #[test]
+    fn test_time_to_travel_guards_division_by_zero() {
+        let mut calc = SpeedProfileCalculator::new(10, 100.0);
+        calc.add_sample(1000.0, 50.0);
+        calc.add_sample(1010.0, 0.0); // effectively stationary segment
+
+        assert!(calc.time_to_travel(1000.0, 1010.0).is_none());
+
+        // Zero-length span is trivially instantaneous, even across a
+        // near-stationary segment
+        assert_eq!(calc.time_to_travel(1005.0, 1005.0), Some(0.0));
+
+        // A single sample can't be integrated over
+        let mut empty_calc = SpeedProfileCalculator::new(10, 100.0);
+        empty_calc.add_sample(1000.0, 50.0);
+        assert!(empty_calc.time_to_travel(1000.0, 1010.0).is_none());
+    }
+
     #[test]
     fn test_reset() {
         let mut calc = SpeedProfileCalculator::new(10, 100.0);
@@ -260,4 +610,19 @@ mod tests {
         calc.reset();
         assert_eq!(calc.window_len(), 0, "Window should be empty after reset");
     }
+
+    #[test]
+    fn test_samples_in_range_counts_only_samples_inside_the_bounds() {
+        let mut calc = SpeedProfileCalculator::new(10, 100.0);
+
+        calc.add_sample(100.0, 50.0);
+        calc.add_sample(200.0, 50.0);
+        calc.add_sample(300.0, 50.0);
+        calc.add_sample(960.0, 50.0);
+        calc.add_sample(970.0, 50.0);
+
+        assert_eq!(calc.window_len(), 5, "all samples should still be in the window");
+        assert_eq!(calc.samples_in_range(950.0, 1000.0), 2, "only the last two samples fall in range");
+        assert_eq!(calc.samples_in_range(1000.0, 950.0), 2, "bounds order shouldn't matter");
+    }
 }
\ No newline at end of file
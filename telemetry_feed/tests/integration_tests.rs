@@ -2,7 +2,7 @@
 /// 
 /// Run with: cargo test --test integration_tests -- --nocapture
 
-use speed_profile_calculator::{SpeedProfileCalculator, SpeedSample};
+use speed_profile_calculator::{ProfileReport, SpeedProfileCalculator, SpeedSample};
 
 #[test]
 fn test_basic_profile_generation() {
@@ -21,8 +21,8 @@ fn test_basic_profile_generation() {
     let profile = calc.get_lookahead_profile(current_distance);
 
     assert!(profile.is_some(), "Profile should be generated");
-    let profile = profile.unwrap();
-    
+    let profile = profile.unwrap().samples;
+
     println!("✓ Generated profile with {} samples", profile.len());
     println!("  First sample: x={:.1}m, v={:.2}m/s", profile[0].x_m, profile[0].v_mps);
     println!("  Last sample:  x={:.1}m, v={:.2}m/s", 
@@ -62,7 +62,7 @@ fn test_target_range_profile() {
     let profile = calc.get_profile(2200.0, 2400.0);
 
     assert!(profile.is_some(), "Target range profile should be generated");
-    let profile = profile.unwrap();
+    let profile = profile.unwrap().samples;
 
     println!("✓ Target range profile: {} samples from {:.1}m to {:.1}m", 
         profile.len(), profile[0].x_m, profile.last().unwrap().x_m);
@@ -108,7 +108,7 @@ fn test_json_serialization() {
     let profile = calc.get_lookahead_profile(3180.0);
     
     assert!(profile.is_some(), "Profile should be generated");
-    let profile = profile.unwrap();
+    let profile = profile.unwrap().samples;
 
     // This should not panic
     let json_str = serde_json::to_string_pretty(&profile)
@@ -154,7 +154,7 @@ fn demo_integration_payload() {
         struct Payload {
             lap_distance_m: f64,
             speed_kph: f64,
-            speed_profile: Option<Vec<SpeedSample>>,
+            speed_profile: Option<ProfileReport>,
         }
 
         let payload = Payload {
@@ -165,20 +165,20 @@ fn demo_integration_payload() {
 
         let json_payload = serde_json::to_string(&payload)
             .expect("Should serialize payload");
-        
+
         let profile_info = speed_profile
             .as_ref()
-            .map(|p| format!("{} samples", p.len()))
+            .map(|p| format!("{} samples", p.samples.len()))
             .unwrap_or_else(|| "None".to_string());
-        
-        println!("\n📡 Sending: dist={}m, speed={}kph, profile={}", 
+
+        println!("\n📡 Sending: dist={}m, speed={}kph, profile={}",
             lap_distance_m, speed_kph, profile_info);
 
         // Show detail on last sample
         if let Some(ref profile) = speed_profile {
             if lap_distance_m == &2215.0 {
-                println!("   Profile range: {:.1}m to {:.1}m", 
-                    profile[0].x_m, profile.last().unwrap().x_m);
+                println!("   Profile range: {:.1}m to {:.1}m",
+                    profile.samples[0].x_m, profile.samples.last().unwrap().x_m);
                 println!("   JSON size: {} bytes", json_payload.len());
             }
         }
@@ -209,7 +209,7 @@ fn test_edge_cases() {
     calc.add_sample(1010.0, 0.0);
     let profile = calc.get_lookahead_profile(1000.0);
     assert!(profile.is_some());
-    for sample in profile.unwrap() {
+    for sample in profile.unwrap().samples {
         assert_eq!(sample.v_mps, 0.0);
     }
     println!("✓ Zero speed handled correctly");
@@ -235,8 +235,8 @@ fn test_high_speed_samples() {
 
     let profile = calc.get_lookahead_profile(1250.0);
     assert!(profile.is_some());
-    
-    let profile = profile.unwrap();
+
+    let profile = profile.unwrap().samples;
     let expected_mps = 320.0 / 3.6; // ~88.89 m/s
     
     for sample in &profile {
@@ -259,9 +259,9 @@ fn test_profile_filtering() {
     // Request profile for a narrow range
     let current = 1500.0;
     let profile = calc.get_lookahead_profile(current);
-    
+
     assert!(profile.is_some());
-    let profile = profile.unwrap();
+    let profile = profile.unwrap().samples;
 
     // All samples should be within lookahead range + buffer
     for sample in &profile {
@@ -298,7 +298,7 @@ fn test_lap_wrap_around() {
     
     // May or may not return a profile depending on samples in range
     if let Some(prof) = profile {
-        println!("✓ Got profile with {} samples near lap boundary", prof.len());
+        println!("✓ Got profile with {} samples near lap boundary", prof.samples.len());
     } else {
         println!("✓ No profile near lap boundary (expected behavior without wrap logic)");
     }
@@ -335,7 +335,7 @@ fn test_realistic_monaco_simulation() {
         let profile = calc.get_lookahead_profile(point);
         assert!(profile.is_some(), "Should have profile at distance {}", point);
         
-        let prof = profile.unwrap();
+        let prof = profile.unwrap().samples;
         println!("  At {:.0}m: {} samples, speed range {:.1}-{:.1} m/s",
             point,
             prof.len(),
@@ -366,7 +366,7 @@ fn test_concurrent_safety() {
             
             let profile = calc.get_lookahead_profile((thread_id * 1000 + 50) as f64);
             assert!(profile.is_some());
-            profile.unwrap().len()
+            profile.unwrap().samples.len()
         })
     }).collect();
     
@@ -406,9 +406,9 @@ fn test_profile_interpolation_readiness() {
 
     let profile = calc.get_lookahead_profile(1020.0);
     assert!(profile.is_some());
-    
-    let prof = profile.unwrap();
-    
+
+    let prof = profile.unwrap().samples;
+
     // Verify we have enough points for trapezoidal integration
     assert!(prof.len() >= 2, "Need at least 2 points for integration");
     